@@ -0,0 +1,28 @@
+//! Compares the direct getters of `NOAASun` with a `precompute` cache.
+//! Run with `cargo bench --features noaa-sun`
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use astronav::coords::noaa_sun::NOAASun;
+
+fn main() {
+    let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(13).min(8);
+    let runs = 100_000;
+
+    let start = Instant::now();
+    for _ in 0..runs {
+        let sun = black_box(&chennai);
+        black_box((sun.altitude_in_deg(), sun.zenith_in_deg(), sun.azimuth_in_deg()));
+    }
+    let direct = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..runs {
+        let cache = black_box(&chennai).precompute();
+        black_box((cache.altitude_in_deg(), cache.zenith_in_deg(), cache.azimuth_in_deg()));
+    }
+    let cached = start.elapsed();
+
+    println!("direct getters: {:?}, precompute: {:?} for {} runs", direct, cached, runs);
+}
@@ -0,0 +1,68 @@
+//! Tolerance based comparisons of floating point results
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! The values returned by this crate are the output of a chain of trigonometric computations,
+//! so comparing them bit for bit breaks as soon as the order of an operation changes.
+//!
+//! # Example
+//! ```
+//! use astronav::approx::{approx_eq, Approx};
+//! use astronav::coords::star::AltAzBuilder;
+//!
+//! // Sirius
+//! let alt_az = AltAzBuilder::new()
+//!             .dec(-16.75122)
+//!             .lat(12.45)
+//!             .lmst(199.05)
+//!             .ra(101.5504)
+//!             .seal()
+//!             .build();
+//!
+//! assert!(approx_eq(-10.6132, alt_az.get_altitude(), 1e-4));
+//! assert_eq!(Approx::new(254.9938).tolerance(1e-4), alt_az.get_azimuth());
+//! ```
+
+/// The tolerance used by `Approx::new` when none is given
+pub const DEFAULT_TOLERANCE: f64 = 1e-9;
+
+/// Returns true if `a` and `b` are not more than `tol` apart
+pub fn approx_eq(a: f64, b: f64, tol: f64) -> bool {
+    (a - b).abs() <= tol
+}
+
+/// A value which is equal to any other value within its tolerance
+#[derive(Debug, Clone, Copy)]
+pub struct Approx<T = f64> {
+    pub value: T,
+    pub tolerance: T,
+}
+
+impl Approx<f64> {
+    /// Wraps a value with the `DEFAULT_TOLERANCE`
+    pub fn new(value: f64) -> Self {
+        Self { value, tolerance: DEFAULT_TOLERANCE }
+    }
+
+    /// Sets the tolerance and returns the Approx
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+}
+
+impl PartialEq<f64> for Approx<f64> {
+    fn eq(&self, other: &f64) -> bool {
+        approx_eq(self.value, *other, self.tolerance)
+    }
+}
+
+impl PartialEq<Approx<f64>> for f64 {
+    fn eq(&self, other: &Approx<f64>) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq for Approx<f64> {
+    fn eq(&self, other: &Approx<f64>) -> bool {
+        approx_eq(self.value, other.value, self.tolerance.max(other.tolerance))
+    }
+}
@@ -0,0 +1,109 @@
+//! Yearly tables of the Sun and the Moon for almanacs
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! # Example
+//! ```
+//! use astronav::coords::{almanac::annual_sun_moon_table, observer::ObserverLocation};
+//!
+//! let chennai = ObserverLocation::new(13.0843, 80.2705);
+//! let table = annual_sun_moon_table(&chennai, 5.5, 2024);
+//!
+//! assert_eq!(366, table.len());
+//! assert_eq!((2024, 5, 17), table[137].date);
+//! ```
+
+use super::math::find_root;
+use super::moon::MoonPosition;
+use super::noaa_sun::solar_day_events;
+use super::observer::ObserverLocation;
+use crate::time::{day_of_year_to_date, is_leap_year, lst_advance, AstroTime};
+
+/// Altitude of the Moon's center at its rise and set, allowing for its parallax, radius and refraction
+const MOONRISE_ALTITUDE: f64 = 0.125;
+
+/// Seconds between the samples of the Moon's altitude
+const STEP_SECS: i64 = 600;
+
+/// The Sun and the Moon for one day, with times in local `Decimal Hours` from midnight of the day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayEphemeris {
+    /// The day as `(year, month, day)`
+    pub date: (u16, u8, u8),
+    /// Sun rise, or None if the Sun does not rise on the day
+    pub sunrise: Option<f64>,
+    /// Solar noon, when the Sun crosses the meridian
+    pub transit: f64,
+    /// Sun set, or None if the Sun does not set on the day
+    pub sunset: Option<f64>,
+    /// Moon rise, or None if the Moon does not rise on the day, as happens about once a month
+    pub moonrise: Option<f64>,
+    /// Moon set, or None if the Moon does not set on the day
+    pub moonset: Option<f64>,
+    /// Illuminated fraction of the Moon's disc at local noon
+    pub moon_illuminated_fraction: f64,
+}
+
+/// Returns the Sun rise, transit and set, the Moon rise and set and the Moon's illuminated fraction for every day of a year.
+///
+/// The Sun comes from the NOAA algorithms of `noaa_sun::solar_day_events`. The Moon's altitude is sampled every 10 minutes
+/// through the whole year, with the sidereal time advanced from the first sample instead of being computed again,
+/// and each sample is shared by the two days it bounds. Only the crossings are then refined, to the second
+///
+/// # Arguments
+/// * `observer`: Location of the observer
+/// * `timezone`: Timezone of the observer in hours (+ east, - west)
+/// * `year`: Year of the table
+pub fn annual_sun_moon_table(observer: &ObserverLocation, timezone: f32, year: u16) -> Vec<DayEphemeris> {
+    let days = if is_leap_year(year) { 366 } else { 365 };
+    let start = AstroTime::new(1, 1, year, 0, 0, 0, timezone);
+    let start_lst = start.lmst_in_degrees(observer.long());
+
+    let moon_height = |secs: f64| {
+        let t = start.plus_seconds(secs.round() as i64);
+        let (ra, dec) = MoonPosition::from(&t).equatorial();
+        let lst = lst_advance(start_lst, secs.round());
+        observer.altaz(ra, dec, lst).get_altitude() - MOONRISE_ALTITUDE
+    };
+
+    let steps_per_day = 86400 / STEP_SECS;
+    let heights: Vec<f64> = (0..=days as i64 * steps_per_day)
+        .map(|step| moon_height((step * STEP_SECS) as f64))
+        .collect();
+
+    (0..days)
+        .map(|day_index| {
+            let doy = day_index + 1;
+            let (month, day) = day_of_year_to_date(year, doy);
+            let sun = solar_day_events(observer, timezone, (year, month, day));
+
+            let (mut moonrise, mut moonset) = (None, None);
+            let first = day_index as i64 * steps_per_day;
+            for step in first..first + steps_per_day {
+                let (before, after) = (heights[step as usize], heights[step as usize + 1]);
+                let crossing = || {
+                    let (lo, hi) = ((step * STEP_SECS) as f64, ((step + 1) * STEP_SECS) as f64);
+                    let secs = find_root(moon_height, lo, hi, 0.5).unwrap_or(hi);
+                    (secs - (first * STEP_SECS) as f64) / 3600.0
+                };
+
+                if before <= 0.0 && after > 0.0 && moonrise.is_none() {
+                    moonrise = Some(crossing());
+                } else if before > 0.0 && after <= 0.0 && moonset.is_none() {
+                    moonset = Some(crossing());
+                }
+            }
+
+            let noon = start.plus_seconds(first * STEP_SECS + 43200);
+
+            DayEphemeris {
+                date: (year, month, day),
+                sunrise: sun.sunrise,
+                transit: sun.noon.expect("the Sun crosses the meridian every day"),
+                sunset: sun.sunset,
+                moonrise,
+                moonset,
+                moon_illuminated_fraction: MoonPosition::from(&noon).illuminated_fraction(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+//! Conversions between celestial coordinate frames
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! # Example
+//! The North Galactic Pole has a galactic latitude of 90°
+//! ```
+//! use astronav::coords::frames::equatorial_to_galactic;
+//!
+//! let (_, b) = equatorial_to_galactic(192.85948, 27.12825);
+//!
+//! assert!((b - 90.0).abs() < 1e-6);
+//! ```
+
+use super::math::{to_cartesian, to_spherical, Mat3};
+
+/// Rotation from the equatorial frame (J2000.0) to the galactic frame, as defined for the Hipparcos catalog
+#[allow(clippy::excessive_precision)]
+const EQUATORIAL_TO_GALACTIC: Mat3 = Mat3([
+    [-0.0548755604162154, -0.8734370902348850, -0.4838350155487132],
+    [0.4941094278755837, -0.4448296299600112, 0.7469822444972189],
+    [-0.8676661490190047, -0.1980763734312015, 0.4559837761750669],
+]);
+
+/// Converts a Right Ascension and a Declination (J2000.0) in `Decimal Degrees` to the
+/// galactic `(longitude, latitude)` in `Decimal Degrees`, the longitude being within `[0, 360)`
+pub fn equatorial_to_galactic(ra: f64, dec: f64) -> (f64, f64) {
+    to_spherical(EQUATORIAL_TO_GALACTIC * to_cartesian(ra, dec))
+}
+
+/// Converts a galactic longitude and latitude in `Decimal Degrees` to the
+/// `(Right Ascension, Declination)` (J2000.0) in `Decimal Degrees`
+pub fn galactic_to_equatorial(l: f64, b: f64) -> (f64, f64) {
+    to_spherical(EQUATORIAL_TO_GALACTIC.transpose() * to_cartesian(l, b))
+}
+
+/// Converts an ecliptic longitude and latitude in `Decimal Degrees` to the `(Right Ascension, Declination)`
+/// in `Decimal Degrees` of the same equinox, given the obliquity of the ecliptic in `Decimal Degrees`
+pub fn ecliptic_to_equatorial(lon: f64, lat: f64, obliquity: f64) -> (f64, f64) {
+    to_spherical(Mat3::rotate_x(obliquity) * to_cartesian(lon, lat))
+}
+
+/// Returns the angular separation in `Decimal Degrees` between two positions given by
+/// their `(Right Ascension, Declination)` in `Decimal Degrees`. Works for any pair of
+/// longitude-like and latitude-like angles of the same frame
+pub fn angular_separation(ra1: f64, dec1: f64, ra2: f64, dec2: f64) -> f64 {
+    let (dec1, dec2) = (dec1.to_radians(), dec2.to_radians());
+    let d_ra = (ra1 - ra2).to_radians();
+
+    // Haversine formula, which stays accurate for small separations
+    let hav = ((dec1 - dec2) / 2.0).sin().powi(2) + dec1.cos() * dec2.cos() * (d_ra / 2.0).sin().powi(2);
+
+    2.0 * hav.sqrt().clamp(0.0, 1.0).asin().to_degrees()
+}
+
+/// Returns the `(Right Ascension, Declination)` in `Decimal Degrees` of the point halfway between two
+/// positions along the great circle joining them, such as the center of a two panel mosaic.
+/// For antipodal positions every great circle through them qualifies, so the point 90° north
+/// of the first position along its hour circle is returned
+pub fn equatorial_midpoint(ra1: f64, dec1: f64, ra2: f64, dec2: f64) -> (f64, f64) {
+    let sum = to_cartesian(ra1, dec1) + to_cartesian(ra2, dec2);
+
+    if sum.norm() > 1e-12 {
+        to_spherical(sum)
+    } else if dec1 > 0.0 {
+        ((ra1 + 180.0).rem_euclid(360.0), 90.0 - dec1)
+    } else {
+        (ra1.rem_euclid(360.0), dec1 + 90.0)
+    }
+}
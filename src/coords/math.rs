@@ -0,0 +1,208 @@
+//! Vectors and rotation matrices used for frame conversions
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! Every conversion between two celestial coordinate frames (equatorial, ecliptic, galactic and
+//! precessed equatorial frames) is a rotation of the unit sphere. Instead of deriving the spherical
+//! trigonometry for each of them, a position is turned into a unit vector, rotated and turned back.
+//!
+//! All angles are in `Decimal Degrees` and all rotations are counter-clockwise (right handed) rotations of the vector.
+//!
+//! # Example
+//! Rotating the vernal equinox by 90° about the z axis lands it on RA 90°
+//! ```
+//! use astronav::coords::math::{to_cartesian, to_spherical, Mat3};
+//!
+//! let v = Mat3::rotate_z(90.0) * to_cartesian(0.0, 0.0);
+//! let (ra, dec) = to_spherical(v);
+//!
+//! assert!((ra - 90.0).abs() < 1e-9);
+//! assert!(dec.abs() < 1e-9);
+//! ```
+
+use std::ops::{Add, Mul};
+
+/// A vector in a three dimensional cartesian frame
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the dot product of two vectors
+    pub fn dot(&self, other: &Vec3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the length of the vector
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+/// A 3x3 matrix stored row by row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3(pub [[f64; 3]; 3]);
+
+impl Mat3 {
+    /// Returns the identity matrix
+    pub fn identity() -> Self {
+        Mat3([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Returns a rotation about the x axis by an angle in `Decimal Degrees`
+    pub fn rotate_x(angle: f64) -> Self {
+        let (s, c) = angle.to_radians().sin_cos();
+        Mat3([[1.0, 0.0, 0.0], [0.0, c, -s], [0.0, s, c]])
+    }
+
+    /// Returns a rotation about the y axis by an angle in `Decimal Degrees`
+    pub fn rotate_y(angle: f64) -> Self {
+        let (s, c) = angle.to_radians().sin_cos();
+        Mat3([[c, 0.0, s], [0.0, 1.0, 0.0], [-s, 0.0, c]])
+    }
+
+    /// Returns a rotation about the z axis by an angle in `Decimal Degrees`
+    pub fn rotate_z(angle: f64) -> Self {
+        let (s, c) = angle.to_radians().sin_cos();
+        Mat3([[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Returns the transpose, which is also the inverse of a rotation matrix
+    pub fn transpose(&self) -> Self {
+        let m = self.0;
+        Mat3([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, rhs: Mat3) -> Mat3 {
+        let mut out = [[0.0; 3]; 3];
+        for (i, row) in out.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.0[i][k] * rhs.0[k][j]).sum();
+            }
+        }
+        Mat3(out)
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let m = self.0;
+        Vec3 {
+            x: m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            y: m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            z: m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        }
+    }
+}
+
+/// Converts a longitude-like angle (RA, ecliptic longitude, galactic longitude) and a latitude-like angle
+/// (Dec, ecliptic latitude, galactic latitude) in `Decimal Degrees` to a unit vector
+pub fn to_cartesian(ra: f64, dec: f64) -> Vec3 {
+    let (sin_ra, cos_ra) = ra.to_radians().sin_cos();
+    let (sin_dec, cos_dec) = dec.to_radians().sin_cos();
+
+    Vec3 {
+        x: cos_dec * cos_ra,
+        y: cos_dec * sin_ra,
+        z: sin_dec,
+    }
+}
+
+/// Converts a vector to `(longitude, latitude)` in `Decimal Degrees`, the longitude being within `[0, 360)`
+pub fn to_spherical(v: Vec3) -> (f64, f64) {
+    let ra = v.y.atan2(v.x).to_degrees().rem_euclid(360.0);
+    // rem_euclid rounds tiny negative angles up to 360.0
+    let ra = if ra >= 360.0 { 0.0 } else { ra };
+    let dec = (v.z / v.norm()).clamp(-1.0, 1.0).asin().to_degrees();
+
+    (ra, dec)
+}
+
+/// Finds where `f` crosses zero between `lo` and `hi`, to within `tol` of the crossing.
+/// Returns None if `f` has the same sign at both ends, as there may be no crossing in between.
+///
+/// Two secant steps through the ends of the bracket (the Illinois variant of regula falsi) are followed by a bisection,
+/// so it converges on smooth functions (altitudes, elongations, longitudes over time) in a few steps, yet the bracket
+/// is at least halved every three steps whatever the shape of `f`
+///
+/// # Example
+/// ```
+/// use astronav::coords::math::find_root;
+///
+/// let x = find_root(|x: f64| x.cos(), 0.0, 3.0, 1e-12).unwrap();
+///
+/// assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+/// ```
+pub fn find_root(f: impl Fn(f64) -> f64, lo: f64, hi: f64, tol: f64) -> Option<f64> {
+    const MAX_ITERATIONS: usize = 300;
+
+    let (mut lo, mut hi) = (lo, hi);
+    let (mut f_lo, mut f_hi) = (f(lo), f(hi));
+
+    if f_lo == 0.0 {
+        return Some(lo);
+    }
+    if f_hi == 0.0 {
+        return Some(hi);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return None;
+    }
+
+    // Which end the last secant step replaced, to halve the value kept at the other end when it sticks
+    let mut last_moved = 0;
+
+    for i in 0..MAX_ITERATIONS {
+        if (hi - lo).abs() <= tol {
+            break;
+        }
+
+        let bisect = i % 3 == 2;
+        let x = if bisect { (lo + hi) / 2.0 } else { (lo * f_hi - hi * f_lo) / (f_hi - f_lo) };
+
+        let f_x = f(x);
+        if f_x == 0.0 {
+            return Some(x);
+        }
+
+        if f_x.signum() == f_lo.signum() {
+            (lo, f_lo) = (x, f_x);
+            if last_moved == -1 && !bisect {
+                f_hi /= 2.0;
+            }
+            last_moved = -1;
+        } else {
+            (hi, f_hi) = (x, f_x);
+            if last_moved == 1 && !bisect {
+                f_lo /= 2.0;
+            }
+            last_moved = 1;
+        }
+    }
+
+    Some((lo + hi) / 2.0)
+}
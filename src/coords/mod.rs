@@ -28,27 +28,181 @@
 //! |Aldebaran|04:35:55.23907|16:30:33.4885|
 //! |Fomalhaut|22:57:39.0465|-29:37:20.050|
 //! |Polaris|02:31:49.09|89:15:50.8|
+//! 
+//! ## Units
+//! The angles taken and returned by the public API are in `Decimal Degrees`, including the Right Ascension and the
+//! sidereal times, except where a name ends in `_in_hours` or `_in_rads`, or the documentation gives another unit.
+//! Times of the day are in `Decimal Hours` or minutes, as documented on each function.
+//! The `AltAzBuilder` setters convert to radians internally, so values in radians must go through `radians_to_degrees` first.
 
 #![deny(clippy::all)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod frames;
+pub mod math;
+pub mod moon;
+pub mod observer;
+pub mod rise_set;
+pub mod seasons;
 pub mod star;
 pub mod sun;
 mod struct_types;
 
+#[cfg(feature = "noaa-sun")]
+#[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
+pub mod almanac;
+
 #[cfg(feature = "noaa-sun")]
 #[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
 pub mod noaa_sun;
 
 
-use std::num::ParseFloatError;
+use std::fmt;
+
+/// Errors that can occur while parsing a `"DD:MM:SS"` or `"HH:MM:SS"` string into its components
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoordParseError {
+    /// The string is empty
+    Empty,
+    /// The string does not have exactly three `:` separated components
+    WrongComponentCount(usize),
+    /// A component is not a number
+    InvalidNumber(String),
+    /// Minutes or seconds are not within `[0, 60)`
+    OutOfRange(String),
+}
+
+impl fmt::Display for CoordParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordParseError::Empty => write!(f, "the string is empty"),
+            CoordParseError::WrongComponentCount(n) => write!(f, "expected 3 components separated by ':', found {}", n),
+            CoordParseError::InvalidNumber(c) => write!(f, "'{}' is not a valid number", c),
+            CoordParseError::OutOfRange(c) => write!(f, "'{}' must be within [0, 60)", c),
+        }
+    }
+}
+
+impl std::error::Error for CoordParseError {}
+
+/// Error returned for a latitude outside `-90..=90` degrees, holding the rejected value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidLatitude(pub f32);
+
+impl fmt::Display for InvalidLatitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "latitude {} is not within -90..=90 degrees", self.0)
+    }
+}
+
+impl std::error::Error for InvalidLatitude {}
+
+/// How the `*_tuple_with` conversions treat the fraction of a second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundMode {
+    /// Keep the seconds as computed, which rounds toward zero and can show `59.9999` seconds.
+    /// This is what the plain `*_tuple` conversions do
+    #[default]
+    Truncate,
+    /// Round to the nearest whole second, carrying a full minute into the minutes and the degrees or hours
+    Nearest,
+}
+
+/**
+ * function to check that a latitude is within `-90..=90` degrees
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::{validate_latitude, InvalidLatitude};
+ * 
+ * assert_eq!(Ok(13.0843), validate_latitude(13.0843));
+ * assert_eq!(Err(InvalidLatitude(95.0)), validate_latitude(95.0));
+ * ```
+**/
+pub fn validate_latitude(lat: f32) -> Result<f32, InvalidLatitude> {
+    if (-90.0..=90.0).contains(&lat) {
+        Ok(lat)
+    } else {
+        Err(InvalidLatitude(lat))
+    }
+}
+
+/**
+ * function to wrap a longitude into `-180..=180` degrees
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::wrap_longitude;
+ * 
+ * assert_eq!(-160.0, wrap_longitude(200.0));
+ * assert_eq!(80.2705, wrap_longitude(80.2705));
+ * ```
+**/
+pub fn wrap_longitude(long: f32) -> f32 {
+    // Values already in range are returned untouched, as the wrapping arithmetic would round them
+    if (-180.0..=180.0).contains(&long) {
+        return long;
+    }
+
+    let long = long.rem_euclid(360.0);
+    if long > 180.0 {
+        long - 360.0
+    } else {
+        long
+    }
+}
+
+/**
+ * function to split Degrees Minutes Seconds into its `(Degrees, Minutes, Seconds)` components
+ * 
+ * # Arguments
+ * * Degrees Minutes Seconds as &str in format *| "DD:MM:SS"* 
+ * 
+ * # Returns
+ * * The signed degrees with the unsigned minutes and seconds. A value between -1° and 0° such as `"-0:30:0"`
+ *   has zero degrees and so loses its sign, check for a leading `-` in that case
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::parse_dms_components;
+ * 
+ * let a = parse_dms_components("-26:29:11.8").unwrap();
+ * 
+ * assert_eq!((-26, 29, 11.8), a);
+ * assert!(parse_dms_components("-26:61:11.8").is_err());
+ * ```
+**/
+pub fn parse_dms_components(dms: &str) -> Result<(i16, u8, f32), CoordParseError> {
+    let a: Vec<&str> = dms.split(':').map(str::trim).collect();
+
+    if a.len() != 3 {
+        return Err(CoordParseError::WrongComponentCount(a.len()));
+    }
+
+    let deg = a[0].parse::<i16>().map_err(|_| CoordParseError::InvalidNumber(a[0].to_owned()))?;
+    let min = a[1].parse::<u8>().map_err(|_| CoordParseError::InvalidNumber(a[1].to_owned()))?;
+    let sec = a[2].parse::<f32>().map_err(|_| CoordParseError::InvalidNumber(a[2].to_owned()))?;
+
+    if min >= 60 {
+        return Err(CoordParseError::OutOfRange(a[1].to_owned()));
+    }
+    if !(0.0..60.0).contains(&sec) {
+        return Err(CoordParseError::OutOfRange(a[2].to_owned()));
+    }
+
+    Ok((deg, min, sec))
+}
 
 /**
  * function to convert Degrees Minutes Seconds to Decimal Degrees
  * 
  * # Arguments
  * * Degrees Minutes Seconds as &str in format *| "DD:MM:SS"* 
- * `(note: Do not pass + before DD in case the DD is a positive number. Pass a - in case it is a negative number)`
+ *   `(note: Do not pass + before DD in case the DD is a positive number. Pass a - in case it is a negative number)`
+ * 
+ * # Errors
+ * * `CoordParseError` if the string is empty, does not have three components, or a component is empty or not a number.
+ *   Whitespace around the string and its components is ignored
  * 
  * # Example
  * ```
@@ -61,20 +215,140 @@ use std::num::ParseFloatError;
  * assert_eq!(14.270055555555556, b);
  * ```
 **/
-pub fn dms_to_deg(dms: &str) -> Result<f64, ParseFloatError> {
-    let is_negative: bool = dms.chars().next().map(|x| x.to_string().as_str() == "-").unwrap();
-    let a: Vec<&str> = dms.split(":").collect::<Vec<&str>>();
+pub fn dms_to_deg(dms: &str) -> Result<f64, CoordParseError> {
+    let [deg, min, sec] = sexagesimal_fields(dms)?;
+
+    if dms.trim().starts_with('-') {
+        Ok(deg - (min / 60.0 + sec / 3600.0))
+    } else {
+        Ok(deg + (min / 60.0 + sec / 3600.0))
+    }
+}
+
+/// Splits a `"DD:MM:SS"` or `"HH:MM:SS"` string into its three components, each parsed as f64.
+/// Whitespace around the string and its components is ignored
+fn sexagesimal_fields(value: &str) -> Result<[f64; 3], CoordParseError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CoordParseError::Empty);
+    }
+
+    let a: Vec<&str> = value.split(':').map(str::trim).collect();
+    if a.len() != 3 {
+        return Err(CoordParseError::WrongComponentCount(a.len()));
+    }
+
+    let parse = |c: &str| {
+        if c.is_empty() || c == "-" {
+            Err(CoordParseError::InvalidNumber(c.to_owned()))
+        } else {
+            c.parse::<f64>().map_err(|_| CoordParseError::InvalidNumber(c.to_owned()))
+        }
+    };
+
+    Ok([parse(a[0])?, parse(a[1])?, parse(a[2])?])
+}
+
+/**
+ * function to convert many Degrees Minutes Seconds strings to Decimal Degrees, keeping the outcome of each
+ * 
+ * # Arguments
+ * * Slice of Degrees Minutes Seconds as &str in format *| "DD:MM:SS"*, as taken by `dms_to_deg`
+ * 
+ * # Returns
+ * * One `Result` per input, in the same order
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::parse_dms_batch;
+ * 
+ * let parsed = parse_dms_batch(&["-26:29:11.8", "not a dms"]);
+ * 
+ * assert_eq!(Ok(-26.48661111111111), parsed[0]);
+ * assert!(parsed[1].is_err());
+ * ```
+**/
+pub fn parse_dms_batch(inputs: &[&str]) -> Vec<Result<f64, CoordParseError>> {
+    inputs.iter().map(|dms| dms_to_deg(dms)).collect()
+}
 
-    if is_negative {
-        Ok(a[0].parse::<f64>()? - 
-        (a[1].parse::<f64>()? / 60.0 + 
-        a[2].parse::<f64>()? / 3600.0))
+/**
+ * function to convert many Degrees Minutes Seconds strings to Decimal Degrees, stopping at the first invalid one
+ * 
+ * # Arguments
+ * * Slice of Degrees Minutes Seconds as &str in format *| "DD:MM:SS"*, as taken by `dms_to_deg`
+ * 
+ * # Errors
+ * * The index of the first invalid string together with its `CoordParseError`
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::{parse_dms_all, CoordParseError};
+ * 
+ * assert_eq!(Ok(vec![-26.48661111111111, 14.270055555555556]), parse_dms_all(&["-26:29:11.8", "14:16:12.2"]));
+ * assert_eq!(Err((1, CoordParseError::Empty)), parse_dms_all(&["-26:29:11.8", ""]));
+ * ```
+**/
+pub fn parse_dms_all(inputs: &[&str]) -> Result<Vec<f64>, (usize, CoordParseError)> {
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, dms)| dms_to_deg(dms).map_err(|e| (i, e)))
+        .collect()
+}
+
+/**
+ * function to convert a UTC offset to decimal hours, for use in the `timezone(...)` setters
+ * 
+ * # Arguments
+ * * UTC offset as &str in format *| "+HH:MM"*, *"-HH:MM"* or *"Z"* for UTC
+ * 
+ * # Errors
+ * * `CoordParseError` if the string is empty, is not a signed `HH:MM`, or the minutes are not within `[0, 60)`.
+ *   Whitespace around the string is ignored
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::parse_timezone;
+ * 
+ * assert_eq!(Ok(5.5), parse_timezone("+05:30"));
+ * assert_eq!(Ok(-4.0), parse_timezone("-04:00"));
+ * assert_eq!(Ok(0.0), parse_timezone("Z"));
+ * ```
+**/
+pub fn parse_timezone(offset: &str) -> Result<f32, CoordParseError> {
+    let offset = offset.trim();
+    if offset.is_empty() {
+        return Err(CoordParseError::Empty);
+    }
+    if offset == "Z" {
+        return Ok(0.0);
+    }
 
+    let invalid = || CoordParseError::InvalidNumber(offset.to_owned());
+    let (sign, rest) = if let Some(rest) = offset.strip_prefix('+') {
+        (1.0, rest)
+    } else if let Some(rest) = offset.strip_prefix('-') {
+        (-1.0, rest)
     } else {
-        Ok(a[0].parse::<f64>()? + 
-        (a[1].parse::<f64>()? / 60.0 + 
-        a[2].parse::<f64>()? / 3600.0))
+        return Err(invalid());
+    };
+    let (hours, mins) = rest.split_once(':').ok_or_else(invalid)?;
+
+    let digits = |c: &str| {
+        if c.len() == 2 && c.bytes().all(|b| b.is_ascii_digit()) {
+            c.parse::<u8>().map_err(|_| invalid())
+        } else {
+            Err(invalid())
+        }
+    };
+    let (hours, mins) = (digits(hours)?, digits(mins)?);
+
+    if mins >= 60 {
+        return Err(CoordParseError::OutOfRange(format!("{:02}", mins)));
     }
+
+    Ok(sign * (hours as f32 + mins as f32 / 60.0))
 }
 
 /**
@@ -82,7 +356,10 @@ pub fn dms_to_deg(dms: &str) -> Result<f64, ParseFloatError> {
  * 
  * # Arguments
  * * Hours Minutes Seconds as &str in format *| "HH:MM:SS"* 
- * `(note: HH must be in 24 hour format)`
+ *   `(note: HH must be in 24 hour format)`
+ * 
+ * # Errors
+ * * `CoordParseError` on the same malformed input as `dms_to_deg`
  * 
  * # Example
  * ```
@@ -91,14 +368,13 @@ pub fn dms_to_deg(dms: &str) -> Result<f64, ParseFloatError> {
  * let a = hms_to_deg("16:30:55.2").unwrap();
  * 
  * assert_eq!(247.73000000000002, a);
+ * assert!(hms_to_deg("16:30").is_err());
  * ```
 **/
-pub fn hms_to_deg(hms: &str) -> Result<f64, ParseFloatError> {
-    let a: Vec<&str> = hms.split(":").collect::<Vec<&str>>();
-        
-    Ok((a[0].parse::<f64>()? + 
-    (a[1].parse::<f64>()? / 60.0 + 
-    a[2].parse::<f64>()? / 3600.0)) * 15.0)
+pub fn hms_to_deg(hms: &str) -> Result<f64, CoordParseError> {
+    let [hours, min, sec] = sexagesimal_fields(hms)?;
+
+    Ok((hours + (min / 60.0 + sec / 3600.0)) * 15.0)
 }
 
 /**
@@ -106,7 +382,7 @@ pub fn hms_to_deg(hms: &str) -> Result<f64, ParseFloatError> {
  * 
  * # Arguments
  * * Hours Minutes Seconds as &str in format *| "HH:MM:SS"* 
- * `(note: HH must be in 24 hour format)`
+ *   `(note: HH must be in 24 hour format)`
  * 
  * # Example
  * ```
@@ -117,7 +393,7 @@ pub fn hms_to_deg(hms: &str) -> Result<f64, ParseFloatError> {
  * assert_eq!("247:43:47.98462".to_string(), a);
  * ```
 **/
-pub fn hms_to_dms(hms: &str) -> Result<String, ParseFloatError> {
+pub fn hms_to_dms(hms: &str) -> Result<String, CoordParseError> {
     let deg = hms_to_deg(hms)?;
     Ok(deg_to_dms(deg as f32))
 }
@@ -127,7 +403,7 @@ pub fn hms_to_dms(hms: &str) -> Result<String, ParseFloatError> {
  * 
  * # Arguments
  * * Hours Minutes Seconds as &str in format *| "HH:MM:SS"* 
- * `(note: HH must be in 24 hour format)`
+ *   `(note: HH must be in 24 hour format)`
  * 
  * # Example
  * ```
@@ -138,7 +414,7 @@ pub fn hms_to_dms(hms: &str) -> Result<String, ParseFloatError> {
  * assert_eq!((247,43,47.98462), a);
  * ```
 **/
-pub fn hms_to_dms_tuple(hms: &str) -> Result<(u8, u8, f32), ParseFloatError> {
+pub fn hms_to_dms_tuple(hms: &str) -> Result<(u8, u8, f32), CoordParseError> {
     let deg = hms_to_deg(hms)?;
     Ok(deg_to_dms_tuple(deg as f32))
 }
@@ -149,19 +425,24 @@ pub fn hms_to_dms_tuple(hms: &str) -> Result<(u8, u8, f32), ParseFloatError> {
  * 
  * # Returns
  * * Hours Minutes Seconds as String in format *| "HH:MM:SS"* 
+ *   `(note: a negative value, such as a hour angle east of the meridian, carries the - on the whole value)`
  * 
  * # Example
  * ```
  * use astronav::coords::hours_to_hms;
  * 
  * let a = hours_to_hms(5.6219597);
+ * let b = hours_to_hms(-5.6219597);
  * 
  * assert_eq!("5:37:19.05487".to_owned(), a);
+ * assert_eq!("-5:37:19.05487".to_owned(), b);
  * ```
 **/
 pub fn hours_to_hms(hours: f32) -> String {
-    let hms = format!("{}:{}:{}", hours.floor(), (hours.fract() * 60.0).floor().abs(), (hours.fract() * 60.0).fract().abs() as f32 * 60.0);
-    hms
+    let sign = if hours < 0.0 { "-" } else { "" };
+    let hours = hours.abs();
+
+    format!("{}{}:{}:{}", sign, hours.floor(), (hours.fract() * 60.0).floor(), (hours.fract() * 60.0).fract() * 60.0)
 }
 
 /**
@@ -180,7 +461,82 @@ pub fn hours_to_hms(hours: f32) -> String {
  * ```
 **/
 pub fn hours_to_hms_tuple(hours: f32) -> (u8, u8, f32) {
-    (hours.floor() as u8, (hours.fract() * 60.0).floor().abs() as u8, (hours.fract() * 60.0).fract().abs() as f32 * 60.0)
+    (hours.floor() as u8, (hours.fract() * 60.0).floor().abs() as u8, (hours.fract() * 60.0).fract().abs() * 60.0)
+}
+
+/**
+ * function to convert Decimal Hours to `(Hours, Minutes, Seconds)` tuple with a choice of rounding
+ * 
+ * # Returns
+ * * Hours Minutes Seconds as a tuple in format *| (HH, MM, SS)* 
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::{hours_to_hms_tuple_with, RoundMode};
+ * 
+ * let a = hours_to_hms_tuple_with(5.6219597, RoundMode::Nearest);
+ * 
+ * assert_eq!((5,37,19.0), a);
+ * ```
+**/
+pub fn hours_to_hms_tuple_with(hours: f32, mode: RoundMode) -> (u8, u8, f32) {
+    match mode {
+        RoundMode::Truncate => hours_to_hms_tuple(hours),
+        RoundMode::Nearest => nearest_sexagesimal_tuple(hours),
+    }
+}
+
+/**
+ * function to convert signed Decimal Hours to `(Hours, Minutes, Seconds, Milliseconds)` tuple
+ *
+ * # Returns
+ * * The input as a tuple in format *| (HH, MM, SS, MS)*, rounded to the nearest millisecond. The hours carry the sign
+ *   and the other parts are the magnitude, so `-1.25` is `(-1, 15, 0, 0)`. Within an hour of zero the hours are 0
+ *   and cannot carry the sign, use `decimal_hours_to_signed_parts` when such values can be negative
+ *
+ * # Example
+ * ```
+ * use astronav::coords::decimal_hours_to_parts;
+ *
+ * let a = decimal_hours_to_parts(-1.25);
+ * let b = decimal_hours_to_parts(5.6219597);
+ *
+ * assert_eq!((-1, 15, 0, 0), a);
+ * assert_eq!((5, 37, 19, 55), b);
+ * ```
+**/
+pub fn decimal_hours_to_parts(hours: f64) -> (i32, u8, u8, u16) {
+    let (negative, whole_hours, min, sec, ms) = decimal_hours_to_signed_parts(hours);
+    let whole_hours = whole_hours as i32;
+
+    (if negative { -whole_hours } else { whole_hours }, min, sec, ms)
+}
+
+/**
+ * function to convert signed Decimal Hours to `(Negative, Hours, Minutes, Seconds, Milliseconds)` tuple
+ *
+ * # Returns
+ * * The sign as a flag, true below zero, with the magnitude as *| (HH, MM, SS, MS)* rounded to the nearest millisecond.
+ *   Unlike `decimal_hours_to_parts` this keeps the sign of values within an hour of zero, such as equation of time offsets
+ *
+ * # Example
+ * ```
+ * use astronav::coords::decimal_hours_to_signed_parts;
+ *
+ * assert_eq!((true, 0, 30, 0, 0), decimal_hours_to_signed_parts(-0.5));
+ * assert_eq!((false, 0, 30, 0, 0), decimal_hours_to_signed_parts(0.5));
+ * ```
+**/
+pub fn decimal_hours_to_signed_parts(hours: f64) -> (bool, u32, u8, u8, u16) {
+    let total_ms = (hours.abs() * 3_600_000.0).round() as u64;
+
+    (
+        hours < 0.0 && total_ms > 0,
+        (total_ms / 3_600_000) as u32,
+        ((total_ms / 60_000) % 60) as u8,
+        ((total_ms / 1000) % 60) as u8,
+        (total_ms % 1000) as u16,
+    )
 }
 
 /**
@@ -199,8 +555,7 @@ pub fn hours_to_hms_tuple(hours: f32) -> (u8, u8, f32) {
  * ```
 **/
 pub fn deg_to_dms(deg: f32) -> String {
-    let dms = format!("{}:{}:{}", deg.floor(), (deg.fract() * 60.0).floor().abs(), (deg.fract() * 60.0).fract().abs() as f32 * 60.0 );
-    dms
+    format!("{}:{}:{}", deg.floor(), (deg.fract() * 60.0).floor().abs(), (deg.fract() * 60.0).fract().abs() * 60.0 )
 }
 
 /**
@@ -219,7 +574,37 @@ pub fn deg_to_dms(deg: f32) -> String {
  * ```
 **/
 pub fn deg_to_dms_tuple(deg: f32) -> (u8, u8, f32) {
-    (deg.floor() as u8, (deg.fract() * 60.0).floor().abs() as u8, (deg.fract() * 60.0).fract().abs() as f32 * 60.0)
+    (deg.floor() as u8, (deg.fract() * 60.0).floor().abs() as u8, (deg.fract() * 60.0).fract().abs() * 60.0)
+}
+
+/**
+ * function to convert Decimal Degrees to `(Degrees, Minutes, Seconds)` tuple with a choice of rounding
+ * 
+ * # Returns
+ * * Degrees Minutes Seconds as a tuple in format *| (DD, MM, SS)* 
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::{deg_to_dms_tuple_with, RoundMode};
+ * 
+ * assert_eq!((12,29,59.996567), deg_to_dms_tuple_with(12.499999, RoundMode::Truncate));
+ * assert_eq!((12,30,0.0), deg_to_dms_tuple_with(12.499999, RoundMode::Nearest));
+ * ```
+**/
+pub fn deg_to_dms_tuple_with(deg: f32, mode: RoundMode) -> (u8, u8, f32) {
+    match mode {
+        RoundMode::Truncate => deg_to_dms_tuple(deg),
+        RoundMode::Nearest => nearest_sexagesimal_tuple(deg),
+    }
+}
+
+/// Splits a value into whole units, minutes and seconds rounded to the nearest second.
+/// The whole units stop at 255, as they do for the truncating conversions
+fn nearest_sexagesimal_tuple(value: f32) -> (u8, u8, f32) {
+    let total_secs = (value.abs() as f64 * 3600.0).round() as u64;
+    let whole = (total_secs / 3600).min(u8::MAX as u64) as u8;
+
+    (whole, ((total_secs / 60) % 60) as u8, (total_secs % 60) as f32)
 }
 
 
@@ -261,4 +646,194 @@ pub fn deg_to_hms_tuple(deg: f32) -> (u8, u8, f32) {
     hours_to_hms_tuple(deg / 15.0)
 }
 
+/**
+ * function to convert Decimal Degrees to `(Hours, Minutes, Seconds)` tuple with a choice of rounding
+ * 
+ * # Returns
+ * * Hours Minutes Seconds as a tuple in format *| (HH, MM, SS)* 
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::{deg_to_hms_tuple_with, RoundMode};
+ * 
+ * let a = deg_to_hms_tuple_with(5.2545, RoundMode::Nearest);
+ * 
+ * assert_eq!((0,21,1.0), a);
+ * ```
+**/
+pub fn deg_to_hms_tuple_with(deg: f32, mode: RoundMode) -> (u8, u8, f32) {
+    hours_to_hms_tuple_with(deg / 15.0, mode)
+}
+
+
+
+/**
+ * function to convert an Azimuth to the name of the nearest of the 16 compass points
+ * 
+ * # Arguments
+ * * Azimuth in | `Decimal Degrees` measured from North towards East, any value is wrapped into `[0, 360)`
+ * 
+ * # Returns
+ * * Compass point such as *| "N", "NNE", "SW"*, each covering 22.5° centered on its direction
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::azimuth_to_compass;
+ * 
+ * assert_eq!("NE", azimuth_to_compass(45.0));
+ * assert_eq!("N", azimuth_to_compass(350.0));
+ * ```
+**/
+pub fn azimuth_to_compass(az: f64) -> &'static str {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW", "NNW",
+    ];
 
+    let index = (az.rem_euclid(360.0) / 22.5).round() as usize % 16;
+    POINTS[index]
+}
+
+/**
+ * function to convert an angle from `Decimal Degrees` to radians, the unit of the trigonometric functions of `f64`
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::degrees_to_radians;
+ * 
+ * assert_eq!(std::f64::consts::PI, degrees_to_radians(180.0));
+ * ```
+**/
+pub fn degrees_to_radians(deg: f64) -> f64 {
+    deg.to_radians()
+}
+
+/**
+ * function to convert an angle from radians to `Decimal Degrees`, the unit of the public API of this crate
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::{radians_to_degrees, star::AltAzBuilder};
+ * 
+ * // A declination known in radians
+ * let dec = radians_to_degrees(-0.2923);
+ * let alt_az = AltAzBuilder::new().dec(dec).lat(12.45).lmst(101.55).ra(101.55).seal().build();
+ * ```
+**/
+pub fn radians_to_degrees(rad: f64) -> f64 {
+    rad.to_degrees()
+}
+
+/**
+ * function to compute the atmospheric refraction (Bennett) that raises a body seen near the horizon
+ * 
+ * # Arguments
+ * * Geometric altitude in | `Decimal Degrees`
+ * 
+ * # Returns
+ * * Refraction in | `Decimal Degrees`, about 0.57° at the horizon and 0.0 below -1°, where the formula no longer holds
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::refraction_in_deg;
+ * 
+ * assert!((refraction_in_deg(0.0) - 0.57).abs() < 0.05);
+ * assert!(refraction_in_deg(45.0) < 0.02);
+ * ```
+**/
+pub fn refraction_in_deg(alt: f64) -> f64 {
+    if alt < -1.0 {
+        return 0.0;
+    }
+
+    // Refraction in arc minutes
+    let r = 1.0 / (alt + 7.31 / (alt + 4.4)).to_radians().tan();
+    r / 60.0
+}
+
+/**
+ * function to compute the geometric dip of the sea-level horizon below the astronomical horizon, for an elevated observer
+ * 
+ * # Arguments
+ * * `elevation_m`: Height of the eye above sea level in | `Meters`
+ * * `earth_radius_km`: Radius of the Earth in | `Kilometers`, such as 6371.0
+ * 
+ * # Returns
+ * * Dip in | `Decimal Degrees`, without the refraction that makes the visible dip a little smaller
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::horizon_dip_degrees;
+ * 
+ * assert!((horizon_dip_degrees(100.0, 6371.0) - 0.321).abs() < 0.001);
+ * ```
+**/
+pub fn horizon_dip_degrees(elevation_m: f64, earth_radius_km: f64) -> f64 {
+    let elevation_km = elevation_m.max(0.0) / 1000.0;
+    (earth_radius_km / (earth_radius_km + elevation_km)).acos().to_degrees()
+}
+
+/**
+ * function to compute the geometric distance from an elevated observer to the sea-level horizon
+ * 
+ * # Arguments
+ * * `elevation_m`: Height of the eye above sea level in | `Meters`
+ * * `earth_radius_km`: Radius of the Earth in | `Kilometers`, such as 6371.0
+ * 
+ * # Returns
+ * * Distance along the line of sight in | `Kilometers`
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::horizon_distance_km;
+ * 
+ * assert!((horizon_distance_km(1.7, 6371.0) - 4.65).abs() < 0.01);
+ * ```
+**/
+pub fn horizon_distance_km(elevation_m: f64, earth_radius_km: f64) -> f64 {
+    let elevation_km = elevation_m.max(0.0) / 1000.0;
+    (elevation_km * (2.0 * earth_radius_km + elevation_km)).sqrt()
+}
+
+/**
+ * function to convert apparent solar time, as read on a sundial, to local mean solar time
+ * 
+ * # Arguments
+ * * `apparent_hours`: Apparent solar time in | `Decimal Hours`
+ * * `eot_minutes`: Equation of time in | `Minutes`, positive when the sundial is ahead of the mean Sun
+ * 
+ * # Returns
+ * * Local mean solar time in | `Decimal Hours` within `[0, 24)`
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::apparent_to_mean_solar;
+ * 
+ * // Early November the sundial runs about 16 minutes fast
+ * assert!((apparent_to_mean_solar(12.0, 16.5) - 11.725).abs() < 1e-9);
+ * ```
+**/
+pub fn apparent_to_mean_solar(apparent_hours: f64, eot_minutes: f64) -> f64 {
+    (apparent_hours - eot_minutes / 60.0).rem_euclid(24.0)
+}
+
+/**
+ * function to convert local mean solar time to apparent solar time, as read on a sundial
+ * 
+ * # Arguments
+ * * `mean_hours`: Local mean solar time in | `Decimal Hours`
+ * * `eot_minutes`: Equation of time in | `Minutes`, positive when the sundial is ahead of the mean Sun
+ * 
+ * # Returns
+ * * Apparent solar time in | `Decimal Hours` within `[0, 24)`
+ * 
+ * # Example
+ * ```
+ * use astronav::coords::mean_to_apparent_solar;
+ * 
+ * // Mid February the sundial runs about 14 minutes slow
+ * assert!((mean_to_apparent_solar(12.0, -14.2) - (12.0 - 14.2 / 60.0)).abs() < 1e-9);
+ * ```
+**/
+pub fn mean_to_apparent_solar(mean_hours: f64, eot_minutes: f64) -> f64 {
+    (mean_hours + eot_minutes / 60.0).rem_euclid(24.0)
+}
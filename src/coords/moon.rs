@@ -0,0 +1,480 @@
+//! Track the Moon positional coordinates
+// Copyright (c) 2024 Venkatesh Omkaram
+
+// Based on the lunar theory in Astronomical Algorithms by Jean Meeus, Chapter 47.
+// Only the larger periodic terms are kept, which gives about 10" in longitude and 4" in latitude.
+
+use super::frames::ecliptic_to_equatorial;
+use super::math::find_root;
+use super::observer::ObserverLocation;
+use super::rise_set::rise_set_iterative;
+use super::sun::sun_ecliptic_longitude;
+use crate::time::{julian_centuries, julian_day_number, julian_time, mean_obliquity_in_degrees, AstroTime};
+
+/// Mean length of the lunation (new moon to new moon) in days
+pub const SYNODIC_MONTH_DAYS: f64 = 29.530588853;
+
+/// Periodic terms for the Moon's longitude and distance: `(D, M, M', F, Σl, Σr)`
+const LONG_DIST_TERMS: [(i8, i8, i8, i8, f64, f64); 32] = [
+    (0, 0, 1, 0, 6288774.0, -20905355.0),
+    (2, 0, -1, 0, 1274027.0, -3699111.0),
+    (2, 0, 0, 0, 658314.0, -2955968.0),
+    (0, 0, 2, 0, 213618.0, -569925.0),
+    (0, 1, 0, 0, -185116.0, 48888.0),
+    (0, 0, 0, 2, -114332.0, -3149.0),
+    (2, 0, -2, 0, 58793.0, 246158.0),
+    (2, -1, -1, 0, 57066.0, -152138.0),
+    (2, 0, 1, 0, 53322.0, -170733.0),
+    (2, -1, 0, 0, 45758.0, -204586.0),
+    (0, 1, -1, 0, -40923.0, -129620.0),
+    (1, 0, 0, 0, -34720.0, 108743.0),
+    (0, 1, 1, 0, -30383.0, 104755.0),
+    (2, 0, 0, -2, 15327.0, 10321.0),
+    (0, 0, 1, 2, -12528.0, 0.0),
+    (0, 0, 1, -2, 10980.0, 79661.0),
+    (4, 0, -1, 0, 10675.0, -34782.0),
+    (0, 0, 3, 0, 10034.0, -23210.0),
+    (4, 0, -2, 0, 8548.0, -21636.0),
+    (2, 1, -1, 0, -7888.0, 24208.0),
+    (2, 1, 0, 0, -6766.0, 30824.0),
+    (1, 0, -1, 0, -5163.0, -8379.0),
+    (1, 1, 0, 0, 4987.0, -16675.0),
+    (2, -1, 1, 0, 4036.0, -12831.0),
+    (2, 0, 2, 0, 3994.0, -10445.0),
+    (4, 0, 0, 0, 3861.0, -11650.0),
+    (2, 0, -3, 0, 3665.0, 14403.0),
+    (0, 1, -2, 0, -2689.0, -7003.0),
+    (2, 0, -1, 2, -2602.0, 0.0),
+    (2, -1, -2, 0, 2390.0, 10056.0),
+    (1, 0, 1, 0, -2348.0, 6322.0),
+    (2, -2, 0, 0, 2236.0, -9884.0),
+];
+
+/// Periodic terms for the Moon's latitude: `(D, M, M', F, Σb)`
+const LAT_TERMS: [(i8, i8, i8, i8, f64); 30] = [
+    (0, 0, 0, 1, 5128122.0),
+    (0, 0, 1, 1, 280602.0),
+    (0, 0, 1, -1, 277693.0),
+    (2, 0, 0, -1, 173237.0),
+    (2, 0, -1, 1, 55413.0),
+    (2, 0, -1, -1, 46271.0),
+    (2, 0, 0, 1, 32573.0),
+    (0, 0, 2, 1, 17198.0),
+    (2, 0, 1, -1, 9266.0),
+    (0, 0, 2, -1, 8822.0),
+    (2, -1, 0, -1, 8216.0),
+    (2, 0, -2, -1, 4324.0),
+    (2, 0, 1, 1, 4200.0),
+    (2, 1, 0, -1, -3359.0),
+    (2, -1, -1, 1, 2463.0),
+    (2, -1, 0, 1, 2211.0),
+    (2, -1, -1, -1, 2065.0),
+    (0, 1, -1, -1, -1870.0),
+    (4, 0, -1, -1, 1828.0),
+    (0, 1, 0, 1, -1794.0),
+    (0, 0, 0, 3, -1749.0),
+    (0, 1, -1, 1, -1565.0),
+    (1, 0, 0, 1, -1491.0),
+    (0, 1, 1, 1, -1475.0),
+    (0, 1, 1, -1, -1410.0),
+    (0, 1, 0, -1, -1344.0),
+    (1, 0, 0, -1, -1335.0),
+    (0, 0, 3, 1, 1107.0),
+    (4, 0, 0, -1, 1021.0),
+    (4, 0, -1, 1, 833.0),
+];
+
+/// A Struct to find the position of the Moon at a given local date and time
+///
+/// # Example
+/// Position of the Moon on April 12th 1992 at 0h UT (Meeus, Example 47.a)
+/// ```
+/// use astronav::coords::moon::MoonPosition;
+///
+/// let moon = MoonPosition::new()
+///             .date(1992, 4, 12)
+///             .hour(0)
+///             .min(0)
+///             .sec(0)
+///             .timezone(0.0);
+///
+/// assert!((moon.ecliptic_longitude() - 133.162655).abs() < 0.02);
+/// assert!((moon.ecliptic_latitude() - -3.229126).abs() < 0.01);
+/// assert!((moon.distance_in_km() - 368409.7).abs() < 50.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MoonPosition {
+    /// Year of interest
+    pub year: u16,
+    /// Month of interest
+    pub month: u8,
+    /// Day of the month
+    pub day: u8,
+    /// Hour of interest (24 hour format)
+    pub hour: u8,
+    /// Minute of interest
+    pub min: u8,
+    /// Second of interest
+    pub sec: u8,
+    /// Timezone of the point of interest in hours (+ east, - west)
+    pub timezone: f32,
+}
+
+impl MoonPosition {
+    /// Provides a default implementation for the value in the struct
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn date(self, year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day, ..self }
+    }
+
+    pub fn hour(self, hour: u8) -> Self {
+        Self { hour, ..self }
+    }
+
+    pub fn min(self, min: u8) -> Self {
+        Self { min, ..self }
+    }
+
+    pub fn sec(self, sec: u8) -> Self {
+        Self { sec, ..self }
+    }
+
+    pub fn timezone(self, timezone: f32) -> Self {
+        Self { timezone, ..self }
+    }
+
+    /// Returns the Julian Time of the instant
+    pub fn julian_time(&self) -> f64 {
+        let jd = julian_day_number(self.day, self.month, self.year);
+        julian_time(jd, self.hour, self.min, self.sec, self.timezone)
+    }
+
+    /// Julian centuries elapsed since J2000.0
+    fn centuries(&self) -> f64 {
+        julian_centuries(self.julian_time())
+    }
+
+    /// Returns the Moon's mean longitude in degrees
+    pub fn mean_longitude(&self) -> f64 {
+        let t = self.centuries();
+        (218.3164477 + 481267.88123421 * t - 0.0015786 * t.powi(2) + t.powi(3) / 538841.0
+            - t.powi(4) / 65194000.0)
+            .rem_euclid(360.0)
+    }
+
+    /// Returns the Moon's mean elongation from the Sun in degrees
+    pub fn mean_elongation(&self) -> f64 {
+        mean_elongation(self.centuries())
+    }
+
+    /// Returns the Sun's mean anomaly in degrees
+    pub fn sun_mean_anomaly(&self) -> f64 {
+        sun_mean_anomaly(self.centuries())
+    }
+
+    /// Returns the Moon's mean anomaly in degrees
+    pub fn mean_anomaly(&self) -> f64 {
+        mean_anomaly(self.centuries())
+    }
+
+    /// Returns the Moon's argument of latitude (mean distance from its ascending node) in degrees
+    pub fn argument_of_latitude(&self) -> f64 {
+        argument_of_latitude(self.centuries())
+    }
+
+    /// Sums the periodic terms in units of 0.000001 degree (latitude, longitude) and 0.001 km (distance)
+    fn periodic_sums(&self) -> (f64, f64, f64) {
+        let t = self.centuries();
+        let l = self.mean_longitude().to_radians();
+        let d = self.mean_elongation().to_radians();
+        let m = self.sun_mean_anomaly().to_radians();
+        let mp = self.mean_anomaly().to_radians();
+        let f = self.argument_of_latitude().to_radians();
+
+        // Correction for the decreasing eccentricity of the Earth's orbit
+        let e = 1.0 - 0.002516 * t - 0.0000074 * t.powi(2);
+        let e_factor = |k: i8| e.powi(k.abs() as i32);
+
+        let a1 = (119.75 + 131.849 * t).to_radians();
+        let a2 = (53.09 + 479264.290 * t).to_radians();
+        let a3 = (313.45 + 481266.484 * t).to_radians();
+
+        let (mut sum_l, sum_r) = LONG_DIST_TERMS.iter().fold(
+            (0.0, 0.0),
+            |(sl, sr), &(td, tm, tmp, tf, cl, cr)| {
+                let arg = td as f64 * d + tm as f64 * m + tmp as f64 * mp + tf as f64 * f;
+                (
+                    sl + cl * e_factor(tm) * arg.sin(),
+                    sr + cr * e_factor(tm) * arg.cos(),
+                )
+            },
+        );
+
+        let mut sum_b = LAT_TERMS.iter().fold(0.0, |sb, &(td, tm, tmp, tf, cb)| {
+            let arg = td as f64 * d + tm as f64 * m + tmp as f64 * mp + tf as f64 * f;
+            sb + cb * e_factor(tm) * arg.sin()
+        });
+
+        // Action of Venus, Jupiter and the flattening of the Earth
+        sum_l += 3958.0 * a1.sin() + 1962.0 * (l - f).sin() + 318.0 * a2.sin();
+        sum_b += -2235.0 * l.sin() + 382.0 * a3.sin() + 175.0 * (a1 - f).sin()
+            + 175.0 * (a1 + f).sin()
+            + 127.0 * (l - mp).sin()
+            - 115.0 * (l + mp).sin();
+        (sum_l, sum_b, sum_r)
+    }
+
+    /// Returns the Moon's geocentric ecliptic longitude in degrees
+    pub fn ecliptic_longitude(&self) -> f64 {
+        (self.mean_longitude() + self.periodic_sums().0 / 1_000_000.0).rem_euclid(360.0)
+    }
+
+    /// Returns the Moon's geocentric ecliptic latitude in degrees.
+    /// An eclipse is only possible at a new or full moon when this is within about ±1.5°
+    pub fn ecliptic_latitude(&self) -> f64 {
+        self.periodic_sums().1 / 1_000_000.0
+    }
+
+    /// Returns the distance between the centers of the Earth and the Moon in kilometers
+    pub fn distance_in_km(&self) -> f64 {
+        385000.56 + self.periodic_sums().2 / 1000.0
+    }
+
+    /// Returns the Moon's elongation in ecliptic longitude east of the Sun in degrees within `[0, 360)`.
+    /// It is 0 at the new moon, 90 at the first quarter, 180 at the full moon and 270 at the last quarter
+    pub fn elongation(&self) -> f64 {
+        (self.ecliptic_longitude() - sun_ecliptic_longitude(self.julian_time())).rem_euclid(360.0)
+    }
+
+    /// Returns the Moon's geocentric `(Right Ascension, Declination)` in degrees, referred to the mean equinox of the date
+    pub fn equatorial(&self) -> (f64, f64) {
+        let obliquity = mean_obliquity_in_degrees(self.julian_time());
+        ecliptic_to_equatorial(self.ecliptic_longitude(), self.ecliptic_latitude(), obliquity)
+    }
+
+    /// Returns the Moon's topocentric `(Right Ascension, Declination)` in degrees, as seen from the surface of the Earth
+    /// rather than from its center. The Moon is close enough for this to shift it by up to about 1°.
+    /// The observer is placed by its geocentric latitude and elevation on the WGS84 ellipsoid (Meeus, Chapter 40)
+    ///
+    /// # Arguments
+    /// * `observer`: Location of the observer
+    /// * `lst`: Local sidereal time of the instant in | `Decimal Degrees`
+    pub fn topocentric_equatorial(&self, observer: &ObserverLocation, lst: f64) -> (f64, f64) {
+        let (ra, dec) = self.equatorial();
+        let sin_parallax = 6378.14 / self.distance_in_km();
+        let (rho_sin, rho_cos) = observer.geocentric_sin_cos();
+        let (sin_ha, cos_ha) = (lst - ra).to_radians().sin_cos();
+        let (sin_dec, cos_dec) = dec.to_radians().sin_cos();
+
+        let denominator = cos_dec - rho_cos * sin_parallax * cos_ha;
+        let d_ra = (-rho_cos * sin_parallax * sin_ha).atan2(denominator);
+        let dec = ((sin_dec - rho_sin * sin_parallax) * d_ra.cos()).atan2(denominator);
+
+        ((ra + d_ra.to_degrees()).rem_euclid(360.0), dec.to_degrees())
+    }
+
+    /// Returns the illuminated fraction of the Moon's disc within `[0, 1]`, from 0 at the new moon to 1 at the full moon.
+    /// The Sun and the Moon are taken to be infinitely far compared to their distance apart, which is good to about 0.2%
+    pub fn illuminated_fraction(&self) -> f64 {
+        let cos_elongation = self.ecliptic_latitude().to_radians().cos() * self.elongation().to_radians().cos();
+
+        (1.0 - cos_elongation) / 2.0
+    }
+}
+
+impl From<&AstroTime> for MoonPosition {
+    fn from(time: &AstroTime) -> Self {
+        Self {
+            year: time.year,
+            month: time.month,
+            day: time.day,
+            hour: time.hour,
+            min: time.min,
+            sec: time.sec,
+            timezone: time.timezone,
+        }
+    }
+}
+
+/// Returns the ecliptic longitude in degrees, within `[0, 360)`, of the true ascending node of the Moon's orbit, where the Moon
+/// crosses the ecliptic going north. The node regresses by about 19.34° a year, one turn in 18.6 years, and its true
+/// position swings by up to 1.5° about the mean one (Meeus, Chapter 47). Eclipses happen when the Sun is near a node.
+/// The node is on the ecliptic, so `frames::ecliptic_to_equatorial` with a latitude of 0° gives its Right Ascension
+/// and Declination
+///
+/// # Arguments
+/// * `jt`: Julian Time on the Terrestrial Time (TT) scale
+///
+/// # Example
+/// ```
+/// use astronav::coords::moon::moon_ascending_node_longitude;
+/// use astronav::coords::sun::sun_ecliptic_longitude;
+/// use astronav::time::AstroTime;
+///
+/// // The total solar eclipse of April 8th 2024 happened near the ascending node
+/// let jt = AstroTime::new(8, 4, 2024, 18, 17, 0, 0.0).julian_time_tt();
+///
+/// assert!((moon_ascending_node_longitude(jt) - sun_ecliptic_longitude(jt)).abs() < 5.0);
+/// ```
+pub fn moon_ascending_node_longitude(jt: f64) -> f64 {
+    let t = julian_centuries(jt);
+    let mean = 125.0445479 - 1934.1362891 * t + 0.0020754 * t.powi(2) + t.powi(3) / 467441.0
+        - t.powi(4) / 60616000.0;
+
+    let d = mean_elongation(t).to_radians();
+    let m = sun_mean_anomaly(t).to_radians();
+    let mp = mean_anomaly(t).to_radians();
+    let f = argument_of_latitude(t).to_radians();
+
+    (mean - 1.4979 * (2.0 * (d - f)).sin() - 0.1500 * m.sin() + 0.1226 * (2.0 * d).sin() + 0.1176 * (2.0 * f).sin()
+        - 0.0801 * (2.0 * (mp - f)).sin())
+    .rem_euclid(360.0)
+}
+
+/// Moon's mean elongation from the Sun in degrees for Julian centuries since J2000.0
+fn mean_elongation(t: f64) -> f64 {
+    (297.8501921 + 445267.1114034 * t - 0.0018819 * t.powi(2) + t.powi(3) / 545868.0
+        - t.powi(4) / 113065000.0)
+        .rem_euclid(360.0)
+}
+
+/// Sun's mean anomaly in degrees for Julian centuries since J2000.0
+fn sun_mean_anomaly(t: f64) -> f64 {
+    (357.5291092 + 35999.0502909 * t - 0.0001536 * t.powi(2) + t.powi(3) / 24490000.0)
+        .rem_euclid(360.0)
+}
+
+/// Moon's mean anomaly in degrees for Julian centuries since J2000.0
+fn mean_anomaly(t: f64) -> f64 {
+    (134.9633964 + 477198.8675055 * t + 0.0087414 * t.powi(2) + t.powi(3) / 69699.0
+        - t.powi(4) / 14712000.0)
+        .rem_euclid(360.0)
+}
+
+/// Moon's argument of latitude in degrees for Julian centuries since J2000.0
+fn argument_of_latitude(t: f64) -> f64 {
+    (93.2720950 + 483202.0175233 * t - 0.0036539 * t.powi(2) - t.powi(3) / 3526000.0
+        + t.powi(4) / 863310000.0)
+        .rem_euclid(360.0)
+}
+
+/// Returns the days elapsed since the most recent new moon, which is the age of the Moon shown by lunar calendars.
+///
+/// The new moon is first placed by the elongation at the Moon's mean rate, then found where the elongation vanishes
+/// by `math::find_root`, so the age follows the true motion of the Moon rather than a fraction of the mean lunation
+///
+/// # Example
+/// ```
+/// use astronav::coords::moon::moon_age_days;
+/// use astronav::time::AstroTime;
+///
+/// // New moon of January 11th 2024 at 11:57 UT
+/// let time = AstroTime::new(12, 1, 2024, 11, 57, 0, 0.0);
+///
+/// assert!((moon_age_days(&time) - 1.0).abs() < 0.05);
+/// ```
+pub fn moon_age_days(time: &AstroTime) -> f64 {
+    // Mean daily motion of the Moon away from the Sun in degrees
+    const ELONGATION_RATE: f64 = 360.0 / SYNODIC_MONTH_DAYS;
+    // The true Moon runs ahead of or behind the mean one by up to about a day over a lunation
+    const WINDOW_SECS: f64 = 3.0 * 86400.0;
+
+    // Elongation `secs` seconds before `time` within `(-180, 180]`, as near the new moon it wraps to almost 360°
+    let elongation_before = |secs: f64| {
+        let elongation = MoonPosition::from(&time.plus_seconds(-(secs.round() as i64))).elongation();
+        if elongation > 180.0 {
+            elongation - 360.0
+        } else {
+            elongation
+        }
+    };
+
+    let estimate = MoonPosition::from(time).elongation() / ELONGATION_RATE * 86400.0;
+    let age_secs = find_root(elongation_before, estimate - WINDOW_SECS, estimate + WINDOW_SECS, 1.0)
+        .map_or(estimate, |secs| secs.round());
+
+    age_secs / 86400.0
+}
+
+/// Returns the phase of the Moon for every day of a month as `(day, illuminated fraction, phase name)`.
+///
+/// The days are taken in UT. A day is named after a principal phase ("New Moon", "First Quarter", "Full Moon",
+/// "Last Quarter") when that phase happens during it, otherwise after the phase in between ("Waxing Crescent",
+/// "Waxing Gibbous", "Waning Gibbous", "Waning Crescent"). The illuminated fraction is taken at 12h UT
+///
+/// # Example
+/// ```
+/// use astronav::coords::moon::moon_phase_calendar;
+///
+/// let calendar = moon_phase_calendar(2024, 1);
+///
+/// assert_eq!(31, calendar.len());
+/// assert_eq!((11, "New Moon"), (calendar[10].0, calendar[10].2));
+/// ```
+pub fn moon_phase_calendar(year: u16, month: u8) -> Vec<(u8, f64, &'static str)> {
+    const PRINCIPAL_PHASES: [(f64, &str); 4] =
+        [(0.0, "New Moon"), (90.0, "First Quarter"), (180.0, "Full Moon"), (270.0, "Last Quarter")];
+
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    let days = julian_day_number(1, next_month, next_year) - julian_day_number(1, month, year);
+
+    (1..=days as u8)
+        .map(|day| {
+            let start = AstroTime::new(day, month, year, 0, 0, 0, 0.0);
+            let start_elongation = MoonPosition::from(&start).elongation();
+            let end_elongation = MoonPosition::from(&start.plus_seconds(86400)).elongation();
+            let advance = (end_elongation - start_elongation).rem_euclid(360.0);
+
+            let noon = MoonPosition::from(&start.plus_seconds(43200));
+            let principal = PRINCIPAL_PHASES
+                .iter()
+                .find(|(target, _)| (target - start_elongation).rem_euclid(360.0) < advance);
+
+            let name = match principal {
+                Some((_, name)) => name,
+                None => match noon.elongation() {
+                    e if e < 90.0 => "Waxing Crescent",
+                    e if e < 180.0 => "Waxing Gibbous",
+                    e if e < 270.0 => "Waning Gibbous",
+                    _ => "Waning Crescent",
+                },
+            };
+
+            (day, noon.illuminated_fraction(), name)
+        })
+        .collect()
+}
+
+/// Altitude of the topocentric center of the Moon at its rise and set, allowing for refraction and the Moon's radius
+const TOPOCENTRIC_MOONRISE_ALTITUDE: f64 = -0.8333;
+
+/// Returns the Moon's azimuth in degrees (measured from the north towards the east) seen from an observer at an instant,
+/// from its topocentric position
+pub fn moon_azimuth_at(time: &AstroTime, observer: &ObserverLocation) -> f64 {
+    let lst = time.lmst_in_degrees(observer.long());
+    let (ra, dec) = MoonPosition::from(time).topocentric_equatorial(observer, lst);
+
+    observer.altaz(ra, dec, lst).get_azimuth()
+}
+
+/// Returns the azimuth in degrees at which the Moon rises during the local day of `date`, or None if it does not rise that day
+pub fn moonrise_azimuth(date: &AstroTime, observer: &ObserverLocation) -> Option<f64> {
+    moon_rise_set(date, observer).0.map(|t| moon_azimuth_at(&t, observer))
+}
+
+/// Returns the azimuth in degrees at which the Moon sets during the local day of `date`, or None if it does not set that day
+pub fn moonset_azimuth(date: &AstroTime, observer: &ObserverLocation) -> Option<f64> {
+    moon_rise_set(date, observer).1.map(|t| moon_azimuth_at(&t, observer))
+}
+
+/// Rise and set of the topocentric Moon during the local day of `date`
+fn moon_rise_set(date: &AstroTime, observer: &ObserverLocation) -> (Option<AstroTime>, Option<AstroTime>) {
+    let position = |t: &AstroTime| {
+        MoonPosition::from(t).topocentric_equatorial(observer, t.lmst_in_degrees(observer.long()))
+    };
+
+    rise_set_iterative(position, TOPOCENTRIC_MOONRISE_ALTITUDE, date, observer)
+}
@@ -3,13 +3,26 @@
 
 use std::f64::consts::PI;
 
-use crate::time::{day_of_year, day_of_year_to_date, julian_day_number, julian_time, AstroTime};
+use super::math::{find_root, to_cartesian, to_spherical, Mat3};
+use super::observer::ObserverLocation;
+use super::star::parallactic_angle;
+use super::{refraction_in_deg, validate_latitude, wrap_longitude, InvalidLatitude};
+use crate::time::{day_of_year, day_of_year_to_date, gmst_in_degrees, julian_day_number, julian_time, lmst_in_degrees, AstroTime};
+
+/// Zenith angle of the Sun's center at sunrise and sunset, allowing for refraction and the Sun's radius
+pub const SUNRISE_ZENITH: f64 = 90.833;
+/// Zenith angle of the Sun at the start of morning and the end of evening civil twilight
+pub const CIVIL_ZENITH: f64 = 96.0;
+/// Zenith angle of the Sun at the start of morning and the end of evening nautical twilight
+pub const NAUTICAL_ZENITH: f64 = 102.0;
+/// Zenith angle of the Sun at the start of morning and the end of evening astronomical twilight
+pub const ASTRONOMICAL_ZENITH: f64 = 108.0;
 
 
 /// A Struct to find the Sun Rise, Sun Set and other items about the Sun using NOAA Algorithms
 /// 
 /// * Note: Using this struct not only helps you to find sun rise and sun set, but you can also find the 
-/// Azimuth and Altitude of the Sun at any point in time. This feature is not available in the `sun` module
+///   Azimuth and Altitude of the Sun at any point in time. This feature is not available in the `sun` module
 /// 
 /// # Example 1
 /// Calculating the Sun Positional Properties on May 17th 2024, Chennai India
@@ -26,6 +39,7 @@ use crate::time::{day_of_year, day_of_year_to_date, julian_day_number, julian_ti
 ///     hour: 13,
 ///     min: 08,
 ///     sec: 47,
+///     millis: 0,
 /// };
 /// 
 /// let fy = chennai_sun.frac_year_by_hour_in_rads();
@@ -45,20 +59,20 @@ use crate::time::{day_of_year, day_of_year_to_date, julian_day_number, julian_ti
 /// 
 /// assert_eq!(2.352617995823504, fy);
 /// assert_eq!(3.575686249705328, eot);
-/// assert_eq!(19.4769, dec);
-/// assert_eq!(15.664421745531797, ha);
-/// assert_eq!("1:2:39.461403".to_owned(), deg_to_hms(ha as f32));
-/// assert_eq!(55.1114358976925, ra);
-/// assert_eq!("3:40:26.744385".to_owned(), deg_to_hms(ra as f32));    
-/// assert_eq!(16.32631546376095, sza);
-/// assert_eq!(73.67368453623905, alt);
-/// assert_eq!(295.10490876949734, saa);
-/// assert_eq!("5:42:50.36476".to_owned(), hours_to_hms(sun_rise as f32));
-/// assert_eq!(342.8394039681132, sun_rise_mins);
+/// assert_eq!(19.392982, dec);
+/// assert_eq!(15.860255078865123, ha);
+/// assert_eq!("1:3:26.46143".to_owned(), deg_to_hms(ha as f32));
+/// assert_eq!(54.915602564359176, ra);
+/// assert_eq!("3:39:39.745102".to_owned(), deg_to_hms(ra as f32));    
+/// assert_eq!(16.470143710638737, sza);
+/// assert_eq!(73.52985628936126, alt);
+/// assert_eq!(294.59822987624136, saa);
+/// assert_eq!("5:42:55.76706".to_owned(), hours_to_hms(sun_rise as f32));
+/// assert_eq!(342.92943925503425, sun_rise_mins);
 /// assert_eq!("12:5:20.539856".to_owned(), hours_to_hms(sun_noon as f32));
 /// assert_eq!(725.3423130178728, sun_noon_mins);
-/// assert_eq!("18:27:50.711517".to_owned(), hours_to_hms(sun_set as f32));
-/// assert_eq!(1107.8452220676324, sun_set_mins);
+/// assert_eq!("18:27:45.314484".to_owned(), hours_to_hms(sun_set as f32));
+/// assert_eq!(1107.7551867807113, sun_set_mins);
 /// ```
 /// # Example 2
 /// We will pass the same parameters as the above example, but using setters
@@ -92,17 +106,17 @@ use crate::time::{day_of_year, day_of_year_to_date, julian_day_number, julian_ti
 /// 
 /// assert_eq!(2.352617995823504, fy);
 /// assert_eq!(3.575686249705328, eot);
-/// assert_eq!(19.4769, dec);
-/// assert_eq!(15.664421745531797, ha);
-/// assert_eq!(16.32631546376095, sza);
-/// assert_eq!(73.67368453623905, alt);
-/// assert_eq!(295.10490876949734, saa);
-/// assert_eq!("5:42:50.36476".to_owned(), hours_to_hms(sun_rise as f32));
-/// assert_eq!(342.8394039681132, sun_rise_mins);
+/// assert_eq!(19.392982, dec);
+/// assert_eq!(15.860255078865123, ha);
+/// assert_eq!(16.470143710638737, sza);
+/// assert_eq!(73.52985628936126, alt);
+/// assert_eq!(294.59822987624136, saa);
+/// assert_eq!("5:42:55.76706".to_owned(), hours_to_hms(sun_rise as f32));
+/// assert_eq!(342.92943925503425, sun_rise_mins);
 /// assert_eq!("12:5:20.539856".to_owned(), hours_to_hms(sun_noon as f32));
 /// assert_eq!(725.3423130178728, sun_noon_mins);
-/// assert_eq!("18:27:50.711517".to_owned(), hours_to_hms(sun_set as f32));
-/// assert_eq!(1107.8452220676324, sun_set_mins);
+/// assert_eq!("18:27:45.314484".to_owned(), hours_to_hms(sun_set as f32));
+/// assert_eq!(1107.7551867807113, sun_set_mins);
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct NOAASun {
@@ -122,6 +136,8 @@ pub struct NOAASun {
     pub min: u8,
     /// Second of interest
     pub sec: u8,
+    /// Milliseconds past the second of interest
+    pub millis: u16,
 }
 
 impl NOAASun {
@@ -135,14 +151,22 @@ impl NOAASun {
         Self { doy, year, ..self}
     }
 
+    /// Sets the longitude, wrapped into `-180..=180` degrees
     pub fn long(self, long: f32) -> Self {
-        Self { long, ..self}
+        Self { long: wrap_longitude(long), ..self }
     }
 
+    /// Sets the latitude as given. Use `try_lat` to reject a latitude outside `-90..=90` degrees,
+    /// which would otherwise make the computations return `NaN`
     pub fn lat(self, lat: f32) -> Self {
         Self { lat, ..self }
     }
 
+    /// Sets the latitude, or returns an error if it is outside `-90..=90` degrees
+    pub fn try_lat(self, lat: f32) -> Result<Self, InvalidLatitude> {
+        Ok(Self { lat: validate_latitude(lat)?, ..self })
+    }
+
     pub fn timezone(self, timezone: f32) -> Self {
         Self { timezone, ..self }
     }
@@ -159,16 +183,15 @@ impl NOAASun {
         Self { sec, ..self }
     }
 
-    /// Computes the fractional day of the year by the hour
+    pub fn millis(self, millis: u16) -> Self {
+        Self { millis, ..self }
+    }
+
+    /// Computes the fractional day of the year by the hour, the days elapsed since 0h UT on January 1st.
+    /// See `time::frac_day_of_year`
     pub fn frac_day_of_year(&self) -> f32 {
-        let days_in_year = if is_leap_year(self.year) {
-            366.0
-        } else {
-            365.0
-        };
         let doy = self.doy;
-        let fy = (doy as f32 / days_in_year) + (doy as f32 - 1.0) - (self.timezone/24.0) + (self.hour as f32/24.0);
-        fy
+        (doy as f32 - 1.0) - (self.timezone/24.0) + (self.hour as f32/24.0)
     }
     
     /// Returns the fractional years in radians for a given year, day of the year, and the hour
@@ -179,9 +202,8 @@ impl NOAASun {
             365.0
         };
 
-        let fy = (2.0 * PI as f64 / days_in_year)
-            * (self.doy as f64 - 1.0 + ((self.hour as f64 - 12.0) / 24.0));
-        fy
+        (2.0 * PI / days_in_year)
+            * (self.doy as f64 - 1.0 + ((self.hour as f64 - 12.0) / 24.0))
     }
 
     /// Returns the fractional years in radians for a given year, day of the year
@@ -192,18 +214,16 @@ impl NOAASun {
             365.0
         };
 
-        let fy = (2.0 * PI as f64/ days_in_year) * (self.doy as f64 - 1.0);
-        fy
+        (2.0 * PI/ days_in_year) * (self.doy as f64 - 1.0)
     }
 
     /// Returns the equation of time in mins for a computed fractional year by hour
     pub fn eot_in_mins_by_frac_year_hour(&self) -> f64 {
-        let eot = 229.18
+        229.18
             * (0.000075 + (0.001868 * self.frac_year_by_hour_in_rads().cos())
                 - (0.032077 * self.frac_year_by_hour_in_rads().sin())
                 - (0.014615 * (2.0 * self.frac_year_by_hour_in_rads()).cos())
-                - (0.040849 * (2.0 * self.frac_year_by_hour_in_rads()).sin()));
-        eot
+                - (0.040849 * (2.0 * self.frac_year_by_hour_in_rads()).sin()))
     }
 
     // /// Returns the equation of time in mins for a computed fractional year
@@ -220,10 +240,8 @@ impl NOAASun {
     pub fn eot_in_mins_by_frac_year(&self) -> f64 {
         let n = 365.0 * (self.year as f64 - 2000.0) + self.doy as f64;
         let mean_anomaly = 6.24004077 + 0.01720197 * n;
-        let eot = -7.659 * mean_anomaly.sin()
-            + 9.863 * ((2.0 * (6.24004077 + 0.01720197 * n) + 3.5932).sin());
-            
-        eot
+        -7.659 * mean_anomaly.sin()
+            + 9.863 * ((2.0 * (6.24004077 + 0.01720197 * n) + 3.5932).sin())
     }
 
     // /// Returns the alternative equation of time in mins
@@ -244,7 +262,8 @@ impl NOAASun {
     pub fn eot_in_mins(&self) -> f64 {
         let month_day = day_of_year_to_date(self.year, self.doy);
         let jd = julian_day_number(month_day.1, month_day.0, self.year);
-        let jt: f64 = (julian_time(jd, self.hour, self.min, self.sec, self.timezone) - 2415020.0)/36525.0;
+        let jt: f64 = (julian_time(jd, self.hour, self.min, self.sec, self.timezone)
+            + self.millis as f64 / 86_400_000.0 - 2415020.0)/36525.0;
         let epsi = (23.452294 - 0.0130125 * jt - 0.00000164_f64 * jt.powi(2) +  0.000000503 * jt.powi(3)).to_radians();
         let y = (epsi /2.0_f64).tan().powi(2);
         let l = (279.69668 + 36000.76892 * jt + 0.0003025 * jt.powi(2)).rem_euclid(360.0).to_radians();
@@ -260,68 +279,79 @@ impl NOAASun {
     }
 
     /// Sun's declination for a given fractional year calculated by hour
+    #[deprecated(since = "0.2.6", note = "use `declination_by_model(DeclinationModel::NoaaSpencer)`")]
     pub fn declination_2(&self) -> f64 {
-        let dec: f64 = 0.006918 - (0.399912 * self.frac_year_by_hour_in_rads().cos())
-            + (0.070257 * self.frac_year_by_hour_in_rads().sin())
-            - (0.006758 * (2.0 * self.frac_year_by_hour_in_rads()).cos())
-            + (0.000907 * (2.0 * self.frac_year_by_hour_in_rads()).sin())
-            - (0.002697 * (3.0 * self.frac_year_by_hour_in_rads()).cos())
-            + (0.00148 * (3.0 * self.frac_year_by_hour_in_rads()).sin());
-
-        dec.to_degrees()
+        self.declination_by_model(DeclinationModel::NoaaSpencer)
     }
 
     /// Sun's declination for a given fractional year by day
+    #[deprecated(since = "0.2.6", note = "use `declination_by_model(DeclinationModel::SpencerByDay)`")]
     pub fn declination_3(&self) -> f64 {
-        let dec: f64 = 0.006918 - (0.399912 * self.frac_year_by_day_in_rads().cos())
-            + (0.070257 * self.frac_year_by_day_in_rads().sin())
-            - (0.006758 * (2.0 * self.frac_year_by_day_in_rads()).cos())
-            + (0.000907 * (2.0 * self.frac_year_by_day_in_rads()).sin())
-            - (0.002697 * (3.0 * self.frac_year_by_day_in_rads()).cos())
-            + (0.00148 * (3.0 * self.frac_year_by_day_in_rads()).sin());
+        self.declination_by_model(DeclinationModel::SpencerByDay)
+    }
 
-        dec.to_degrees()
+    /// Sun's declination in degrees computed with the chosen `DeclinationModel`
+    pub fn declination_by_model(&self, model: DeclinationModel) -> f64 {
+        match model {
+            DeclinationModel::NoaaSpencer => spencer_declination(self.frac_year_by_hour_in_rads()),
+            DeclinationModel::SpencerByDay => spencer_declination(self.frac_year_by_day_in_rads()),
+            DeclinationModel::AccurateCosine => self.declination() as f64,
+        }
     }
 
     // {\displaystyle \delta _{\odot }=-\arcsin \left[0.39779\cos \left(0.98565^{\circ }\left(N+10\right)+1.914^{\circ }\sin \left(0.98565^{\circ }\left(N-2\right)\right)\right)\right]}
     /// Sun's declination for a given fractional day of the year (This is more accurate)
     pub fn declination(&self) -> f32 {
-        let frac_day_of_year = self.frac_day_of_year();
-        let a = 0.985653269 * (frac_day_of_year + 10.0);
-        let b = 1.913679036 * (0.985653269 * (frac_day_of_year - 2.0)).to_radians().sin();
-        let c = -(0.397776944 * (a + b).to_radians().cos()).asin();
+        declination_by_frac_day(self.frac_day_of_year())
+    }
+
+    /// Returns how many minutes the apparent solar time, as read on a sundial, is ahead of the clock (negative when behind).
+    /// This is the equation of time plus 4 minutes for every degree the observer is east of the meridian of the timezone
+    pub fn solar_clock_offset_minutes(&self) -> f64 {
+        self.clock_offset_by_eot(self.eot_in_mins())
+    }
 
-        c.to_degrees()
+    /// `solar_clock_offset_minutes` for an equation of time already computed
+    fn clock_offset_by_eot(&self, eot: f64) -> f64 {
+        eot + (4.0 * self.long as f64) - 60.0 * self.timezone as f64
     }
 
     /// Returns the Sun hour angle in degrees for a given longitude and time
     pub fn ha_in_deg(&self) -> f64 {
-        let time_offset =
-            self.eot_in_mins() + (4.0 * self.long as f64) - 60.0 * self.timezone as f64;
-        let true_solar_time = ((self.hour as u32 * 60) + self.min as u32 + (self.sec as u32 / 60))
-            as f64
-            + time_offset;
+        self.ha_by_eot(self.eot_in_mins())
+    }
+
+    /// `ha_in_deg` for an equation of time already computed
+    fn ha_by_eot(&self, eot: f64) -> f64 {
+        let mins = ((self.hour as u32 * 60) + self.min as u32) as f64
+            + (self.sec as f64 / 60.0)
+            + (self.millis as f64 / 60_000.0);
+
+        ha_at_minutes(self.clock_offset_by_eot(eot), mins)
+    }
 
-        let mut hour_angle = (true_solar_time / 4.0) - 180.0;
+    /// Returns the Sun hour angle in hours within `[-12, 12)`, negative east of the meridian (before solar noon)
+    /// and positive west of it, like `AltAz::get_hour_angle` does for the stars
+    pub fn ha_signed_hours(&self) -> f64 {
+        let ha = self.ha_in_deg() / 15.0;
 
-        if hour_angle < 0.0 {
-            hour_angle += 360.0;
+        if ha >= 12.0 {
+            ha - 24.0
+        } else {
+            ha
         }
+    }
 
-        hour_angle
+    /// Returns the parallactic angle of the Sun in degrees within `(-180, 180]`, negative in the morning, positive in the afternoon
+    /// and 0 at solar noon unless the Sun culminates between the zenith and the pole (see `star::parallactic_angle`).
+    /// Solar imagers on alt-azimuth mounts de-rotate their frames by it
+    pub fn parallactic_angle(&self) -> f64 {
+        parallactic_angle(self.ha_in_deg(), self.declination() as f64, self.lat as f64)
     }
 
     /// Returns the Zenith Angle of the sun for a given declination, latitude, and hour angle
     pub fn zenith_in_deg(&self) -> f64 {
-        let dec = self.declination() as f64;
-        let lat = self.lat as f64;
-        let sza = ((lat.to_radians().sin() * dec.to_radians().sin())
-            + (lat.to_radians().cos()
-                * dec.to_radians().cos()
-                * self.ha_in_deg().to_radians().cos()))
-        .acos();
-
-        sza.to_degrees()
+        zenith_by_ha(self.lat as f64, self.declination() as f64, self.ha_in_deg())
     }
 
     /// Returns the Altitude of the sun for a given declination, latitude, and hour angle
@@ -329,24 +359,174 @@ impl NOAASun {
         90.0 - self.zenith_in_deg()
     }
 
-    /// Returns the Azimuth angle of the sun for a given declination, latitude and zenith angle
-    pub fn azimuth_in_deg(&self) -> f64 {
+    /// Returns the apparent Altitude of the sun, which is the geometric altitude raised by atmospheric refraction (Bennett).
+    /// This is the altitude an observer actually sees, so the visible sunrise happens when it crosses 0°.
+    /// The refraction is about 0.57° at the horizon and is not applied below -1°, where the formula no longer holds
+    pub fn apparent_altitude_in_deg(&self) -> f64 {
+        let h = self.altitude_in_deg();
+        h + refraction_in_deg(h)
+    }
+
+    /// Returns true if the sun is above the given altitude in degrees.
+    /// Pass a negative altitude to test against a point below the horizon, for example `-6.0` for civil twilight
+    pub fn is_sun_above(&self, altitude_deg: f64) -> bool {
+        self.altitude_in_deg() > altitude_deg
+    }
+
+    /// Returns the maximum Altitude of the sun for the day, reached at solar noon, using the declination and latitude
+    pub fn max_altitude(&self) -> f64 {
         let dec = self.declination() as f64;
         let lat = self.lat as f64;
-        let sza = self.zenith_in_deg();
-        let sha = self.ha_in_deg();
 
-        let saa: f64 = -(((lat.to_radians().sin() * sza.to_radians().cos())
-            - dec.to_radians().sin())
-            / (lat.to_radians().cos() * sza.to_radians().sin()));
+        90.0 - (lat - dec).abs()
+    }
+
+    /// Returns the local time in mins at which the sun is highest in the day. This differs from `noon_mins` by a few seconds,
+    /// as the declination keeps changing while the sun crosses the meridian: the sun is still climbing at the transit
+    /// when it is heading towards the observer's latitude. The declination is taken to change at the `dec_rate` of the noon,
+    /// and the peak is found as the root of the rate of change of the altitude
+    pub fn max_altitude_time(&self) -> f64 {
+        const STEP_MINS: f64 = 0.25;
+
+        let noon = self.noon_mins();
+        let at_noon = self.at_local_mins(noon);
+        let (dec, dec_rate) = (at_noon.declination() as f64, at_noon.dec_rate());
+        let lat = self.lat as f64;
+
+        let altitude = |mins: f64| {
+            let dec = dec + dec_rate * (mins - noon) / 60.0;
+            90.0 - zenith_by_ha(lat, dec, self.at_local_mins(mins).ha_in_deg())
+        };
+        let slope = |mins: f64| altitude(mins + STEP_MINS) - altitude(mins - STEP_MINS);
+
+        find_root(slope, noon - 30.0, noon + 30.0, 1e-4).unwrap_or(noon)
+    }
+
+    /// Returns a copy of the Sun at the given minutes since local midnight of the same date, to the millisecond
+    fn at_local_mins(&self, mins: f64) -> NOAASun {
+        let mins = mins.rem_euclid(1440.0);
+        NOAASun {
+            hour: (mins / 60.0) as u8,
+            min: (mins % 60.0) as u8,
+            sec: 0,
+            millis: ((mins % 1.0) * 60_000.0) as u16,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the Azimuth angle of the sun for a given declination, latitude and zenith angle
+    pub fn azimuth_in_deg(&self) -> f64 {
+        azimuth_by_zenith(self.lat as f64, self.declination() as f64, self.zenith_in_deg(), self.ha_in_deg())
+    }
+
+    /// Returns the Azimuth angle of the sun in degrees at its rise, or None if it does not rise on the day
+    pub fn sunrise_azimuth(&self) -> Option<f64> {
+        let ha = self.ha_for_zenith(SUNRISE_ZENITH)?;
+        Some(azimuth_by_zenith(self.lat as f64, self.declination() as f64, SUNRISE_ZENITH, 360.0 - ha))
+    }
 
-        if sha > 180.0 {
-            saa.acos().to_degrees()
+    /// Returns the Azimuth angle of the sun in degrees at its set, or None if it does not set on the day
+    pub fn sunset_azimuth(&self) -> Option<f64> {
+        let ha = self.ha_for_zenith(SUNRISE_ZENITH)?;
+        Some(azimuth_by_zenith(self.lat as f64, self.declination() as f64, SUNRISE_ZENITH, ha))
+    }
+
+    /// Returns the angle in degrees the sun sweeps in azimuth from its rise to its set, the range of motion a solar
+    /// tracker needs. It passes through the south when it culminates south of the zenith and through the north otherwise.
+    /// This is 360.0 when the sun does not set on the day and 0.0 when it does not rise
+    pub fn azimuth_swing(&self) -> f64 {
+        let (Some(rise), Some(set)) = (self.sunrise_azimuth(), self.sunset_azimuth()) else {
+            return if self.cos_ha_for_zenith(SUNRISE_ZENITH) < -1.0 { 360.0 } else { 0.0 };
+        };
+
+        if (self.lat as f64) >= self.declination() as f64 {
+            set - rise
         } else {
-            360.0 - saa.acos().to_degrees()
+            360.0 - (set - rise)
         }
     }
 
+    /// Returns the Altitude of the antisolar point, the point of the sky directly opposite the Sun.
+    /// It is above the horizon whenever the Sun is below it
+    pub fn antisolar_altitude(&self) -> f64 {
+        -self.altitude_in_deg()
+    }
+
+    /// Returns the Azimuth angle of the antisolar point, 180° away from the Sun's, within `[0, 360)`
+    pub fn antisolar_azimuth(&self) -> f64 {
+        (self.azimuth_in_deg() + 180.0).rem_euclid(360.0)
+    }
+
+    /// Returns the Sun's path across the day as `(minutes since local midnight, altitude, azimuth)` taken every `step_minutes`.
+    /// The declination and the equation of time are computed once, as they change very little within a day.
+    /// Returns None if `step_minutes` is not a positive number of minutes
+    pub fn arc(&self, step_minutes: f64) -> Option<Vec<(f64, f64, f64)>> {
+        // A zero step would ask for endless positions, a negative (or NaN) one for none
+        if step_minutes.is_nan() || step_minutes <= 0.0 {
+            return None;
+        }
+
+        let dec = self.declination() as f64;
+        let lat = self.lat as f64;
+        let time_offset = self.clock_offset_by_eot(self.eot_in_mins());
+
+        let steps = (1440.0 / step_minutes).ceil() as usize;
+
+        let arc = (0..steps)
+            .map(|i| {
+                let mins = i as f64 * step_minutes;
+                let ha = ha_at_minutes(time_offset, mins);
+                let sza = zenith_by_ha(lat, dec, ha);
+
+                (mins, 90.0 - sza, azimuth_by_zenith(lat, dec, sza, ha))
+            })
+            .collect();
+
+        Some(arc)
+    }
+
+    /// Returns the Sun's `(hour, altitude, azimuth)` at each of the given local times, in hours since local midnight of the date.
+    /// Like `arc`, the declination and the equation of time are computed once, at the time the Sun is set to
+    pub fn positions_at(&self, hours: &[f64]) -> Vec<(f64, f64, f64)> {
+        let dec = self.declination() as f64;
+        let lat = self.lat as f64;
+        let time_offset = self.clock_offset_by_eot(self.eot_in_mins());
+
+        hours
+            .iter()
+            .map(|&hour| {
+                let ha = ha_at_minutes(time_offset, hour * 60.0);
+                let sza = zenith_by_ha(lat, dec, ha);
+
+                (hour, 90.0 - sza, azimuth_by_zenith(lat, dec, sza, ha))
+            })
+            .collect()
+    }
+
+    /// Returns the sun's `(label, altitude, azimuth)` in degrees at five points of the day: sunrise, mid-morning
+    /// (hour angle -45°), noon, mid-afternoon (hour angle +45°) and sunset, enough to sketch its path.
+    /// The declination is taken once, like `arc` does. When the sun does not rise or set on the day,
+    /// the first and the last entries are at local midnight instead
+    pub fn cardinal_positions(&self) -> [(String, f64, f64); 5] {
+        let dec = self.declination() as f64;
+        let lat = self.lat as f64;
+        let rise_ha = self.ha_for_zenith(SUNRISE_ZENITH).unwrap_or(180.0);
+
+        [
+            ("sunrise", -rise_ha),
+            ("mid-morning", -45.0),
+            ("noon", 0.0),
+            ("mid-afternoon", 45.0),
+            ("sunset", rise_ha),
+        ]
+        .map(|(label, ha): (&str, f64)| {
+            let ha = ha.rem_euclid(360.0);
+            let sza = zenith_by_ha(lat, dec, ha);
+
+            (label.to_owned(), 90.0 - sza, azimuth_by_zenith(lat, dec, sza, ha))
+        })
+    }
+
     pub fn sunrise_time_hours(&self) -> f64 {
         self.sunrise_time_mins() / 60.0
     }
@@ -394,44 +574,787 @@ impl NOAASun {
         1440.0 - (4.0 * (long + ha.to_degrees())) - eot + (self.timezone as f64 * 60.0)
     }
 
+    /// Returns the Sun rise as a fractional day of the year on the local clock, for example `137.24` for 5:46 on day 137.
+    /// The day moves back or forward with the event, so it is unambiguous for plotting over a year
+    pub fn sunrise_frac_doy(&self) -> f64 {
+        self.doy as f64 + self.sunrise_time_mins() / 1440.0
+    }
+
+    /// Returns the Sun set as a fractional day of the year on the local clock, for example `138.002` for 0:03
+    /// on the day after `doy`
+    pub fn sunset_frac_doy(&self) -> f64 {
+        self.doy as f64 + self.sunset_time_mins() / 1440.0
+    }
+
+    /// Returns the Sun rise time in mins from UTC midnight of the date, leaving the timezone to the display.
+    /// The value is not wrapped, so it is negative when the Sun rises on the previous day in UTC
+    pub fn sunrise_time_mins_utc(&self) -> f64 {
+        self.local_mins_to_utc(self.sunrise_time_mins())
+    }
+
+    /// Returns the solar noon in mins from UTC midnight of the date, not wrapped into `0..1440`
+    pub fn noon_mins_utc(&self) -> f64 {
+        self.local_mins_to_utc(self.noon_mins())
+    }
+
+    /// Returns the Sun set time in mins from UTC midnight of the date.
+    /// The value is not wrapped, so it is above 1440 when the Sun sets on the next day in UTC
+    pub fn sunset_time_mins_utc(&self) -> f64 {
+        self.local_mins_to_utc(self.sunset_time_mins())
+    }
+
+    /// Returns the time in mins from UTC midnight of the date at which the Sun reaches the given zenith angle in the morning,
+    /// or None if it never does on the day. See `morning_time_mins_by_zenith`
+    pub fn morning_time_mins_by_zenith_utc(&self, zenith: f64) -> Option<f64> {
+        self.morning_time_mins_by_zenith(zenith).map(|mins| self.local_mins_to_utc(mins))
+    }
+
+    /// Returns the time in mins from UTC midnight of the date at which the Sun reaches the given zenith angle in the evening,
+    /// or None if it never does on the day. See `evening_time_mins_by_zenith`
+    pub fn evening_time_mins_by_zenith_utc(&self, zenith: f64) -> Option<f64> {
+        self.evening_time_mins_by_zenith(zenith).map(|mins| self.local_mins_to_utc(mins))
+    }
+
+    /// Converts minutes from local midnight to minutes from UTC midnight of the same date
+    fn local_mins_to_utc(&self, mins: f64) -> f64 {
+        mins - self.timezone as f64 * 60.0
+    }
+
     pub fn day_length(&self) -> f64 {
         self.sunset_time_hours() - self.sunrise_time_hours()
     }
 
+    /// Returns the length of the day in hours between the crossings of the true horizon by the Sun's center,
+    /// without the refraction and the Sun's radius that `day_length` allows for, or None if the Sun does not cross it.
+    /// The difference with `day_length` is the time these add to the day, a few minutes at mid-latitudes
+    pub fn geometric_day_length(&self) -> Option<f64> {
+        let ha = self.ha_for_zenith(90.0)?;
+        Some(8.0 * ha / 60.0)
+    }
+
+    /// Returns the Local Mean Sidereal Time in degrees at the moment of Sun rise, useful to pre-slew a mount
+    pub fn lst_at_sunrise(&self) -> f64 {
+        self.lst_at_local_mins(self.sunrise_time_mins())
+    }
+
+    /// Returns the Local Mean Sidereal Time in degrees at the solar transit, which is the Sun's Right Ascension then.
+    /// This is the sidereal time to command an equatorial mount with to catch the Sun on the meridian
+    pub fn transit_lst(&self) -> f64 {
+        self.lst_at_local_mins(self.noon_mins())
+    }
+
+    /// Returns the Local Mean Sidereal Time in degrees at the moment of Sun set
+    pub fn lst_at_sunset(&self) -> f64 {
+        self.lst_at_local_mins(self.sunset_time_mins())
+    }
+
+    /// Returns the Local Mean Sidereal Time in degrees at the given minutes since local midnight of the date
+    fn lst_at_local_mins(&self, mins: f64) -> f64 {
+        let month_day = day_of_year_to_date(self.year, self.doy);
+        let jd = julian_day_number(month_day.1, month_day.0, self.year);
+        let jt = julian_time(jd, 0, 0, 0, self.timezone) + mins / 1440.0;
+
+        lmst_in_degrees(gmst_in_degrees(jt), self.long as f64)
+    }
+
+    /// Returns the Sun's hour angle in degrees when it reaches the given zenith angle,
+    /// or None if the Sun never reaches it on the day
+    fn ha_for_zenith(&self, zenith: f64) -> Option<f64> {
+        let cos_ha = self.cos_ha_for_zenith(zenith);
+
+        if (-1.0..=1.0).contains(&cos_ha) {
+            Some(cos_ha.acos().to_degrees())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cosine of the Sun's hour angle when it reaches the given zenith angle.
+    /// It is above 1 when the Sun stays below that zenith angle all day, and below -1 when it stays above it
+    fn cos_ha_for_zenith(&self, zenith: f64) -> f64 {
+        let dec = self.declination() as f64;
+        let lat = self.lat as f64;
+
+        (zenith.to_radians().cos() / (lat.to_radians().cos() * dec.to_radians().cos()))
+            - (lat.to_radians().tan() * dec.to_radians().tan())
+    }
+
+    /// Returns the fraction of the day, within `[0, 1]`, during which the Sun is above the horizon.
+    /// This is 1.0 during the polar day and 0.0 during the polar night
+    pub fn daylight_fraction(&self) -> f64 {
+        let cos_ha = self.cos_ha_for_zenith(SUNRISE_ZENITH).clamp(-1.0, 1.0);
+
+        cos_ha.acos().to_degrees() / 180.0
+    }
+
+    /// Returns the local Sun rise time in mins for an observer whose horizon is raised by `horizon_altitude_deg` degrees,
+    /// or None if the Sun never rises above that horizon on the day
+    pub fn sunrise_time_mins_by_horizon(&self, horizon_altitude_deg: f64) -> Option<f64> {
+        self.morning_time_mins_by_zenith(SUNRISE_ZENITH - horizon_altitude_deg)
+    }
+
+    /// Returns the local Sun set time in mins for an observer whose horizon is raised by `horizon_altitude_deg` degrees,
+    /// or None if the Sun never sets below that horizon on the day
+    pub fn sunset_time_mins_by_horizon(&self, horizon_altitude_deg: f64) -> Option<f64> {
+        self.evening_time_mins_by_zenith(SUNRISE_ZENITH - horizon_altitude_deg)
+    }
+
+    /// Returns the local time in mins at which the Sun reaches the given zenith angle in the morning.
+    /// Pass one of `SUNRISE_ZENITH`, `CIVIL_ZENITH`, `NAUTICAL_ZENITH` or `ASTRONOMICAL_ZENITH`.
+    /// This returns None if the Sun never reaches the zenith angle on the day
+    pub fn morning_time_mins_by_zenith(&self, zenith: f64) -> Option<f64> {
+        let ha = self.ha_for_zenith(zenith)?;
+        Some(self.noon_mins() - 4.0 * ha)
+    }
+
+    /// Returns the local time in mins at which the Sun reaches the given zenith angle in the evening.
+    /// Pass one of `SUNRISE_ZENITH`, `CIVIL_ZENITH`, `NAUTICAL_ZENITH` or `ASTRONOMICAL_ZENITH`.
+    /// This returns None if the Sun never reaches the zenith angle on the day
+    pub fn evening_time_mins_by_zenith(&self, zenith: f64) -> Option<f64> {
+        let ha = self.ha_for_zenith(zenith)?;
+        Some(self.noon_mins() + 4.0 * ha)
+    }
+
+    /// Same as `morning_time_mins_by_zenith`, but tells apart the two reasons the Sun may not reach the zenith angle.
+    /// At high latitudes astronomical twilight never ending in summer and the Sun never rising in winter both lack the event
+    pub fn morning_twilight_by_zenith(&self, zenith: f64) -> TwilightTime {
+        match self.morning_time_mins_by_zenith(zenith) {
+            Some(mins) => TwilightTime::At(mins),
+            None => self.missing_twilight(zenith),
+        }
+    }
+
+    /// Same as `evening_time_mins_by_zenith`, but tells apart the two reasons the Sun may not reach the zenith angle
+    pub fn evening_twilight_by_zenith(&self, zenith: f64) -> TwilightTime {
+        match self.evening_time_mins_by_zenith(zenith) {
+            Some(mins) => TwilightTime::At(mins),
+            None => self.missing_twilight(zenith),
+        }
+    }
+
+    /// Which side of the zenith angle the Sun stays on all day, when it never reaches it
+    fn missing_twilight(&self, zenith: f64) -> TwilightTime {
+        if self.cos_ha_for_zenith(zenith) > 1.0 {
+            TwilightTime::AlwaysBelowThreshold
+        } else {
+            TwilightTime::AlwaysAboveThreshold
+        }
+    }
+
     pub fn ra_in_deg(&self) -> f64 {
+        let lst = self.astro_time().lmst_in_degrees(self.long.into());
+        lst - self.ha_in_deg()
+    }
+
+    /// Returns the distance between the Earth and the Sun in astronomical units
+    pub fn distance_in_au(&self) -> f64 {
+        let n = self.astro_time().julian_time() - 2451545.0;
+        let g = (357.529 + 0.98560028 * n).rem_euclid(360.0).to_radians();
+
+        1.00014 - 0.01671 * g.cos() - 0.00014 * (2.0 * g).cos()
+    }
+
+    /// Returns the position of the Sun and the times of the day's events, all computed for this same instant
+    pub fn report(&self) -> SunReport {
+        SunReport {
+            altitude: self.altitude_in_deg(),
+            azimuth: self.azimuth_in_deg(),
+            ra: self.ra_in_deg(),
+            dec: self.declination() as f64,
+            hour_angle: self.ha_in_deg(),
+            distance_au: self.distance_in_au(),
+            eot_minutes: self.eot_in_mins(),
+            rise: self.sunrise_time_mins(),
+            transit: self.noon_mins(),
+            set: self.sunset_time_mins(),
+        }
+    }
+
+    /// Computes the declination, the equation of time, the hour angle and the zenith angle once, for the getters of
+    /// `SunCache` to share. Each of `altitude_in_deg`, `zenith_in_deg` and `azimuth_in_deg` computes them all again
+    pub fn precompute(&self) -> SunCache {
+        let lat = self.lat as f64;
+        let dec = self.declination();
+        let eot = self.eot_in_mins();
+        let ha = self.ha_by_eot(eot);
+
+        SunCache {
+            lat,
+            dec,
+            eot,
+            ha,
+            zenith: zenith_by_ha(lat, dec as f64, ha),
+        }
+    }
+
+    /// Returns the rate of change of the Sun's Right Ascension in degrees per hour, about 1° a day.
+    /// Use it to interpolate the Sun between coarse time steps
+    pub fn ra_rate(&self) -> f64 {
+        let (before, after) = (self.shifted_by_hours(-1), self.shifted_by_hours(1));
+        let d_ra = (after.ra_in_deg() - before.ra_in_deg()).rem_euclid(360.0);
+        let d_ra = if d_ra > 180.0 { d_ra - 360.0 } else { d_ra };
+
+        d_ra / 2.0
+    }
+
+    /// Returns the rate of change of the Sun's declination in degrees per hour,
+    /// positive from the December to the June solstice
+    pub fn dec_rate(&self) -> f64 {
+        let (before, after) = (self.shifted_by_hours(-1), self.shifted_by_hours(1));
+
+        (after.declination() as f64 - before.declination() as f64) / 2.0
+    }
+
+    /// Returns a copy of the Sun moved by a number of hours, rolling the date over as needed
+    fn shifted_by_hours(&self, hours: i64) -> NOAASun {
+        let t = self.astro_time().plus_seconds(hours * 3600);
+        NOAASun {
+            year: t.year,
+            doy: day_of_year(t.year, t.month, t.day),
+            hour: t.hour,
+            min: t.min,
+            sec: t.sec,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the instant of interest as an AstroTime
+    fn astro_time(&self) -> AstroTime {
         let doy_to_date = day_of_year_to_date(self.year, self.doy);
-        let at = AstroTime { 
-            day: doy_to_date.1,
-            month: doy_to_date.0, 
-            year: self.year, 
-            hour: self.hour, 
-            min: self.min, 
-            sec: self.sec,
-            timezone: self.timezone 
-        };
+        AstroTime::new(doy_to_date.1, doy_to_date.0, self.year, self.hour, self.min, self.sec, self.timezone)
+            .with_millis(self.millis)
+    }
+}
+
+/// Hour angle of the Sun in degrees within `[0, 360)` at `mins` minutes past local midnight on the clock,
+/// given how many minutes the apparent solar time is ahead of the clock (see `solar_clock_offset_minutes`)
+fn ha_at_minutes(clock_offset: f64, mins: f64) -> f64 {
+    ((mins + clock_offset) / 4.0 - 180.0).rem_euclid(360.0)
+}
+
+/// Zenith angle in degrees for a given latitude, declination and hour angle in degrees
+fn zenith_by_ha(lat: f64, dec: f64, ha: f64) -> f64 {
+    let sza = ((lat.to_radians().sin() * dec.to_radians().sin())
+        + (lat.to_radians().cos()
+            * dec.to_radians().cos()
+            * ha.to_radians().cos()))
+    .acos();
+
+    sza.to_degrees()
+}
+
+/// Azimuth angle in degrees for a given latitude, declination, zenith angle and hour angle in degrees
+fn azimuth_by_zenith(lat: f64, dec: f64, sza: f64, sha: f64) -> f64 {
+    let saa: f64 = -(((lat.to_radians().sin() * sza.to_radians().cos())
+        - dec.to_radians().sin())
+        / (lat.to_radians().cos() * sza.to_radians().sin()));
+
+    if sha > 180.0 {
+        saa.acos().to_degrees()
+    } else {
+        360.0 - saa.acos().to_degrees()
+    }
+}
+
+/// The crossings of the Sun rise, Sun set and twilight zenith angles in a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwilightEvent {
+    AstronomicalDawn,
+    NauticalDawn,
+    CivilDawn,
+    Sunrise,
+    Sunset,
+    CivilDusk,
+    NauticalDusk,
+    AstronomicalDusk,
+}
+
+/// The local time at which the Sun reaches a zenith angle, or the side of it the Sun stays on when it does not
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TwilightTime {
+    /// The Sun reaches the zenith angle at this local time in mins
+    At(f64),
+    /// The Sun stays higher than the zenith angle all day, as in the white nights of a polar summer
+    AlwaysAboveThreshold,
+    /// The Sun stays lower than the zenith angle all day, as in a polar night
+    AlwaysBelowThreshold,
+}
+
+impl TwilightTime {
+    /// Returns the local time in mins, or None if the Sun does not reach the zenith angle on the day
+    pub fn mins(&self) -> Option<f64> {
+        match self {
+            TwilightTime::At(mins) => Some(*mins),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the twilight events and the Sun rise and Sun set of a day for an observer as `(event, local Decimal Hours)`,
+/// sorted by time. The events the Sun does not reach on the day, such as the dusks of a polar summer, are left out
+///
+/// # Arguments
+/// * `timezone`: Timezone of the observer in hours (+ east, - west)
+/// * `date`: The day as `(year, month, day)`
+///
+/// # Example
+/// ```
+/// use astronav::coords::{noaa_sun::{twilight_schedule, TwilightEvent}, observer::ObserverLocation};
+///
+/// let chennai = ObserverLocation::new(13.0843, 80.2705);
+/// let schedule = twilight_schedule(&chennai, 5.5, (2024, 5, 17));
+///
+/// assert_eq!(8, schedule.len());
+/// assert_eq!(TwilightEvent::AstronomicalDawn, schedule[0].0);
+/// ```
+pub fn twilight_schedule(observer: &ObserverLocation, timezone: f32, date: (u16, u8, u8)) -> Vec<(TwilightEvent, f64)> {
+    let events = solar_day_events(observer, timezone, date);
+
+    let mut schedule: Vec<(TwilightEvent, f64)> = [
+        (TwilightEvent::AstronomicalDawn, events.astronomical_dawn),
+        (TwilightEvent::NauticalDawn, events.nautical_dawn),
+        (TwilightEvent::CivilDawn, events.civil_dawn),
+        (TwilightEvent::Sunrise, events.sunrise),
+        (TwilightEvent::Sunset, events.sunset),
+        (TwilightEvent::CivilDusk, events.civil_dusk),
+        (TwilightEvent::NauticalDusk, events.nautical_dusk),
+        (TwilightEvent::AstronomicalDusk, events.astronomical_dusk),
+    ]
+    .into_iter()
+    .filter_map(|(event, time)| time.map(|time| (event, time)))
+    .collect();
+
+    schedule.sort_by(|a, b| a.1.total_cmp(&b.1));
+    schedule
+}
+
+/// Returns how many minutes before the Sun rise the morning civil twilight begins for an observer on a date given as
+/// `(year, month, day)`, or None if either does not happen on the day
+///
+/// # Example
+/// ```
+/// use astronav::coords::{noaa_sun::civil_dawn_offset_minutes, observer::ObserverLocation};
+///
+/// let chennai = ObserverLocation::new(13.0843, 80.2705);
+/// let offset = civil_dawn_offset_minutes(&chennai, (2024, 5, 17)).unwrap();
+///
+/// assert!((20.0..25.0).contains(&offset));
+/// ```
+pub fn civil_dawn_offset_minutes(observer: &ObserverLocation, date: (u16, u8, u8)) -> Option<f64> {
+    // The offset does not depend on the timezone
+    let events = solar_day_events(observer, 0.0, date);
+
+    Some((events.sunrise? - events.civil_dawn?) * 60.0)
+}
+
+/// The formulas `NOAASun` can compute the Sun's declination with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclinationModel {
+    /// Spencer's Fourier series, as used by NOAA, on the fractional year including the hour.
+    /// Accurate to about 0.035° (0.0006 rad)
+    NoaaSpencer,
+    /// Spencer's Fourier series on the fractional year at the start of the day, ignoring the hour.
+    /// The declination can change by up to 0.4° within a day around the equinoxes, so this is the least accurate
+    SpencerByDay,
+    /// The arcsine of a cosine of the Earth's position, corrected for the eccentricity of its orbit, on the fractional day
+    /// including the hour and the timezone. This is the model `NOAASun::declination` uses and the most accurate of the three
+    AccurateCosine,
+}
+
+/// The intermediate values of the position of the Sun at an instant, returned by `NOAASun::precompute`.
+/// The getters return the same values as the `NOAASun` methods of the same names
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunCache {
+    lat: f64,
+    dec: f32,
+    eot: f64,
+    ha: f64,
+    zenith: f64,
+}
+
+impl SunCache {
+    /// Returns the Sun's declination in degrees
+    pub fn declination(&self) -> f32 {
+        self.dec
+    }
+
+    /// Returns the equation of time in minutes
+    pub fn eot_in_mins(&self) -> f64 {
+        self.eot
+    }
+
+    /// Returns the Sun hour angle in degrees
+    pub fn ha_in_deg(&self) -> f64 {
+        self.ha
+    }
+
+    /// Returns the Zenith Angle of the sun in degrees
+    pub fn zenith_in_deg(&self) -> f64 {
+        self.zenith
+    }
+
+    /// Returns the Altitude of the sun in degrees
+    pub fn altitude_in_deg(&self) -> f64 {
+        90.0 - self.zenith
+    }
+
+    /// Returns the Azimuth angle of the sun in degrees
+    pub fn azimuth_in_deg(&self) -> f64 {
+        azimuth_by_zenith(self.lat, self.dec as f64, self.zenith, self.ha)
+    }
+}
+
+/// The position of the Sun at an instant with the times of the day's events, returned by `NOAASun::report`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SunReport {
+    /// Altitude in degrees
+    pub altitude: f64,
+    /// Azimuth in degrees
+    pub azimuth: f64,
+    /// Right Ascension in degrees
+    pub ra: f64,
+    /// Declination in degrees
+    pub dec: f64,
+    /// Hour angle in degrees
+    pub hour_angle: f64,
+    /// Distance from the Earth in astronomical units
+    pub distance_au: f64,
+    /// Equation of time in minutes
+    pub eot_minutes: f64,
+    /// Local Sun rise time in minutes
+    pub rise: f64,
+    /// Local solar noon in minutes
+    pub transit: f64,
+    /// Local Sun set time in minutes
+    pub set: f64,
+}
+
+impl From<&AstroTime> for NOAASun {
+    /// Takes the instant from an AstroTime, leaving the observer at latitude and longitude 0
+    fn from(time: &AstroTime) -> Self {
+        Self {
+            year: time.year,
+            doy: time.day_of_year(),
+            long: 0.0,
+            lat: 0.0,
+            timezone: time.timezone,
+            hour: time.hour,
+            min: time.min,
+            sec: time.sec,
+            millis: time.millis(),
+        }
+    }
+}
+
+impl SunReport {
+    /// Returns the column names of `to_csv_row`, in the order of the fields of the struct
+    pub fn csv_header() -> &'static str {
+        "altitude,azimuth,ra,dec,hour_angle,distance_au,eot_minutes,rise,transit,set"
+    }
 
-        let lst = at.lmst_in_degrees(self.long.into());
-        let ra = lst - self.ha_in_deg();
-        ra
+    /// Returns the report as a comma separated line, with the columns given by `csv_header`
+    pub fn to_csv_row(&self) -> String {
+        [
+            self.altitude,
+            self.azimuth,
+            self.ra,
+            self.dec,
+            self.hour_angle,
+            self.distance_au,
+            self.eot_minutes,
+            self.rise,
+            self.transit,
+            self.set,
+        ]
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
     }
 }
 
+/// The Sun rise, Sun set, solar noon and twilight times of a day in local `Decimal Hours`.
+/// An event is None when the Sun does not reach the corresponding zenith angle on the day
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SolarDayEvents {
+    pub astronomical_dawn: Option<f64>,
+    pub nautical_dawn: Option<f64>,
+    pub civil_dawn: Option<f64>,
+    pub sunrise: Option<f64>,
+    pub noon: Option<f64>,
+    pub sunset: Option<f64>,
+    pub civil_dusk: Option<f64>,
+    pub nautical_dusk: Option<f64>,
+    pub astronomical_dusk: Option<f64>,
+}
+
+impl SolarDayEvents {
+    /// Returns the column names of `to_csv_row`, in the order of the fields of the struct
+    pub fn csv_header() -> &'static str {
+        "astronomical_dawn,nautical_dawn,civil_dawn,sunrise,noon,sunset,civil_dusk,nautical_dusk,astronomical_dusk"
+    }
+
+    /// Returns the events as a comma separated line, with the columns given by `csv_header`.
+    /// An event that does not happen on the day is left empty
+    pub fn to_csv_row(&self) -> String {
+        [
+            self.astronomical_dawn,
+            self.nautical_dawn,
+            self.civil_dawn,
+            self.sunrise,
+            self.noon,
+            self.sunset,
+            self.civil_dusk,
+            self.nautical_dusk,
+            self.astronomical_dusk,
+        ]
+        .iter()
+        .map(|v| v.map(|v| v.to_string()).unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join(",")
+    }
+}
+
+/// Computes the Sun rise, Sun set, solar noon and the civil, nautical and astronomical twilights in one call
+///
+/// # Arguments
+/// * `observer`: Location of the point of interest
+/// * `timezone`: Timezone of the point of interest in hours (+ east, - west)
+/// * `date`: Local date as `(year, month, day)`
+///
+/// # Example
+/// ```
+/// use astronav::coords::{noaa_sun::solar_day_events, observer::ObserverLocation};
+///
+/// // Chennai, May 17th 2024
+/// let chennai = ObserverLocation::new(13.0843, 80.2705);
+/// let events = solar_day_events(&chennai, 5.5, (2024, 5, 17));
+///
+/// assert!(events.civil_dawn.unwrap() < events.sunrise.unwrap());
+/// assert!(events.sunset.unwrap() < events.civil_dusk.unwrap());
+/// ```
+pub fn solar_day_events(observer: &ObserverLocation, timezone: f32, date: (u16, u8, u8)) -> SolarDayEvents {
+    solar_day_events_by_horizon(observer, timezone, date, 0.0)
+}
+
+/// Same as `solar_day_events`, but for an observer whose horizon is raised by `horizon_altitude_deg` degrees.
+/// Every rise, set and twilight threshold is shifted up by the same amount
+pub fn solar_day_events_by_horizon(
+    observer: &ObserverLocation,
+    timezone: f32,
+    date: (u16, u8, u8),
+    horizon_altitude_deg: f64,
+) -> SolarDayEvents {
+    let (year, month, day) = date;
+    let sun = NOAASun::new()
+        .date(year, month, day)
+        .lat(observer.lat() as f32)
+        .long(observer.long() as f32)
+        .timezone(timezone)
+        .hour(12);
+
+    let morning = |zenith: f64| {
+        sun.morning_time_mins_by_zenith(zenith - horizon_altitude_deg)
+            .map(|mins| mins / 60.0)
+    };
+    let evening = |zenith: f64| {
+        sun.evening_time_mins_by_zenith(zenith - horizon_altitude_deg)
+            .map(|mins| mins / 60.0)
+    };
+
+    SolarDayEvents {
+        astronomical_dawn: morning(ASTRONOMICAL_ZENITH),
+        nautical_dawn: morning(NAUTICAL_ZENITH),
+        civil_dawn: morning(CIVIL_ZENITH),
+        sunrise: morning(SUNRISE_ZENITH),
+        noon: Some(sun.noon_hours()),
+        sunset: evening(SUNRISE_ZENITH),
+        civil_dusk: evening(CIVIL_ZENITH),
+        nautical_dusk: evening(NAUTICAL_ZENITH),
+        astronomical_dusk: evening(ASTRONOMICAL_ZENITH),
+    }
+}
+
+/// Computes the sub-solar point, the place on the Earth where the Sun is at the zenith at the given instant
+///
+/// # Returns
+/// `(latitude, longitude)` in degrees, the longitude being within `(-180, 180]` (+ east, - west)
+pub fn sub_solar_point(time: &AstroTime) -> (f64, f64) {
+    let sun = NOAASun::from(time);
+
+    // The hour angle at Greenwich is how far west of Greenwich the Sun stands
+    let long = -sun.ha_in_deg();
+    let long = if long <= -180.0 { long + 360.0 } else { long };
+
+    (sun.declination() as f64, long)
+}
+
+/// Computes the terminator, the day/night boundary, as the great circle lying 90° away from the sub-solar point
+///
+/// # Arguments
+/// * `time`: Instant of interest
+/// * `step_deg`: Angular spacing in degrees between the consecutive points along the great circle
+///
+/// # Returns
+/// `(latitude, longitude)` pairs in degrees, the longitude being within `(-180, 180]` (+ east, - west),
+/// or None if `step_deg` is not a positive number of degrees
+///
+/// # Example
+/// ```
+/// use astronav::coords::noaa_sun::terminator_points;
+/// use astronav::time::AstroTime;
+///
+/// let time = AstroTime::new(17, 5, 2024, 13, 8, 47, 5.5);
+/// let points = terminator_points(&time, 1.0).unwrap();
+///
+/// assert_eq!(360, points.len());
+/// ```
+pub fn terminator_points(time: &AstroTime, step_deg: f64) -> Option<Vec<(f64, f64)>> {
+    // A zero step would ask for endless points, a negative (or NaN) one for none
+    if step_deg.is_nan() || step_deg <= 0.0 {
+        return None;
+    }
+
+    let (sub_lat, sub_long) = sub_solar_point(time);
+    // Carries the pole of the equator onto the sub-solar point, and with it the equator onto the terminator
+    let rotation = Mat3::rotate_z(sub_long) * Mat3::rotate_y(90.0 - sub_lat);
+
+    let steps = (360.0 / step_deg).round() as usize;
+
+    let points = (0..steps)
+        .map(|i| {
+            let (long, lat) = to_spherical(rotation * to_cartesian(i as f64 * step_deg, 0.0));
+            let long = if long > 180.0 { long - 360.0 } else { long };
+            (lat, long)
+        })
+        .collect();
+
+    Some(points)
+}
+
+/// Computes the geometric altitude of the Sun in degrees over a latitude/longitude grid at one instant,
+/// from the sub-solar point and the spherical law of cosines, so the Sun's position is computed only once
+///
+/// # Arguments
+/// * `time`: Instant of interest
+/// * `lat_step`: Spacing in degrees between the rows, which run from latitude -90 up to 90
+/// * `long_step`: Spacing in degrees between the columns, which run from longitude -180 eastwards, short of 180
+///
+/// # Returns
+/// One row of altitudes per latitude, each holding one altitude per longitude, or None if either step is not a
+/// positive number of degrees
+///
+/// # Example
+/// ```
+/// use astronav::coords::noaa_sun::solar_altitude_grid;
+/// use astronav::time::AstroTime;
+///
+/// let time = AstroTime::new(17, 5, 2024, 13, 8, 47, 5.5);
+/// let grid = solar_altitude_grid(&time, 10.0, 10.0).unwrap();
+///
+/// assert_eq!(19, grid.len());
+/// assert_eq!(36, grid[0].len());
+/// ```
+pub fn solar_altitude_grid(time: &AstroTime, lat_step: f64, long_step: f64) -> Option<Vec<Vec<f64>>> {
+    // A zero step would ask for endless rows or columns, a negative (or NaN) one for none
+    if !(lat_step > 0.0 && long_step > 0.0) {
+        return None;
+    }
+
+    let (sub_lat, sub_long) = sub_solar_point(time);
+    let (sin_dec, cos_dec) = sub_lat.to_radians().sin_cos();
+
+    let rows = (180.0 / lat_step).floor() as usize + 1;
+    let columns = (360.0 / long_step).ceil() as usize;
+
+    // The cosine of the hour angle depends only on the longitude, so it is shared by all the rows
+    let cos_ha: Vec<f64> = (0..columns)
+        .map(|j| (-180.0 + j as f64 * long_step - sub_long).to_radians().cos())
+        .collect();
+
+    let grid = (0..rows)
+        .map(|i| {
+            let (sin_lat, cos_lat) = (-90.0 + i as f64 * lat_step).to_radians().sin_cos();
+            cos_ha
+                .iter()
+                .map(|c| (sin_lat * sin_dec + cos_lat * cos_dec * c).clamp(-1.0, 1.0).asin().to_degrees())
+                .collect()
+        })
+        .collect();
+
+    Some(grid)
+}
+
+/// Equation of time by W. M. Smart for every day of a year as `(day of the year, minutes)`, taken at 12h UT.
+/// The curve stays within about ±16 minutes, with minima in February and July and maxima in May and November
+///
+/// # Example
+/// ```
+/// use astronav::coords::noaa_sun::equation_of_time_year;
+///
+/// let eot = equation_of_time_year(2023);
+///
+/// assert_eq!(365, eot.len());
+/// assert_eq!(1, eot[0].0);
+/// ```
+pub fn equation_of_time_year(year: u16) -> Vec<(u16, f64)> {
+    let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+
+    (1..=days_in_year)
+        .map(|doy| (doy, eot_in_mins(year, doy, 12, 0, 0, 0.0)))
+        .collect()
+}
+
+/// Sun's declination in degrees by the Fourier series of J. W. Spencer for a fractional year in radians
+fn spencer_declination(frac_year: f64) -> f64 {
+    let dec: f64 = 0.006918 - (0.399912 * frac_year.cos())
+        + (0.070257 * frac_year.sin())
+        - (0.006758 * (2.0 * frac_year).cos())
+        + (0.000907 * (2.0 * frac_year).sin())
+        - (0.002697 * (3.0 * frac_year).cos())
+        + (0.00148 * (3.0 * frac_year).sin());
+
+    dec.to_degrees()
+}
+
+/// Sun's declination in degrees at the start (0h UT) of a given day of the year.
+/// This is the same computation as `NOAASun::declination`, so tables of the Sun's noon altitude over a year
+/// can be built without constructing a `NOAASun` per day. The declination does not depend on the length of the year
+///
+/// # Example
+/// ```
+/// use astronav::coords::noaa_sun::solar_declination_for_day;
+///
+/// // June solstice
+/// assert!((solar_declination_for_day(172) - 23.44).abs() < 0.05);
+/// ```
+pub fn solar_declination_for_day(doy: u16) -> f64 {
+    let frac_day_of_year = doy as f32 - 1.0;
+
+    declination_by_frac_day(frac_day_of_year) as f64
+}
+
+/// Sun's declination in degrees for a fractional day of the year
+#[allow(clippy::excessive_precision)]
+fn declination_by_frac_day(frac_day_of_year: f32) -> f32 {
+    let a = 0.985653269 * (frac_day_of_year + 10.0);
+    let b = 1.913679036 * (0.985653269 * (frac_day_of_year - 2.0)).to_radians().sin();
+    let c = -(0.397776944 * (a + b).to_radians().cos()).asin();
+
+    c.to_degrees()
+}
+
 /// Checks if a year is leap year
 pub fn is_leap_year(year: u16) -> bool {
-    if (year % 4 == 0 && !(year % 100 == 0)) || (year % 400 == 0) {
-        true
-    } else {
-        false
-    }
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
 }
 
 #[allow(unused)]
 /// Equation of time by year and day of the year
 pub fn eot_in_mins_2(y: u16, doy: u16) -> f64 {
     let t = 365.0 * (y as f64 - 2000.0) + doy as f64;
-    let eot = -7.659 * (6.24004077 + 0.01720197 * t).sin()
-        + 9.863 * (2.0 * (6.24004077 + 0.01720197 * t) + 3.5932).sin();
-    eot
+    -7.659 * (6.24004077 + 0.01720197 * t).sin()
+        + 9.863 * (2.0 * (6.24004077 + 0.01720197 * t) + 3.5932).sin()
 }
 
 #[allow(unused)]
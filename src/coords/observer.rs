@@ -0,0 +1,136 @@
+//! Describe the observer's location on the Earth
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! # Example
+//! The trigonometry of the latitude is computed once, when the ObserverLocation is created,
+//! and reused for every star looked up from the same site.
+//! ```
+//! use astronav::coords::observer::ObserverLocation;
+//!
+//! let chennai = ObserverLocation::new(12.45, 80.2705);
+//!
+//! // Sirius
+//! let alt_az = chennai.altaz(101.5504, -16.75122, 199.05);
+//!
+//! assert_eq!(-10.613191752481162, alt_az.get_altitude());
+//! assert_eq!(254.99375998808006, alt_az.get_azimuth());
+//! ```
+
+use super::star::AltAz;
+
+/// A location on the Earth from where the sky is observed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObserverLocation {
+    lat: f64,
+    long: f64,
+    sin_lat: f64,
+    cos_lat: f64,
+    elevation_m: f64,
+}
+
+impl ObserverLocation {
+    /// Creates an ObserverLocation by a latitude (+ north, - south) and a longitude (+ east, - west) in `Decimal Degrees`
+    pub fn new(lat: f64, long: f64) -> Self {
+        let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+        Self { lat, long, sin_lat, cos_lat, elevation_m: 0.0 }
+    }
+
+    /// Sets the height of the observer above sea level in `Meters`, used by the parallax of the Moon
+    pub fn with_elevation(self, elevation_m: f64) -> Self {
+        Self { elevation_m, ..self }
+    }
+
+    /// Returns the height of the observer above sea level in `Meters`
+    pub fn elevation(&self) -> f64 {
+        self.elevation_m
+    }
+
+    /// Returns `(ρ sin φ', ρ cos φ')`, where φ' is the geocentric latitude of the observer and ρ its distance from the
+    /// center of the Earth in equatorial radii (WGS84). These place the observer for the parallax corrections
+    pub fn geocentric_sin_cos(&self) -> (f64, f64) {
+        geocentric_sin_cos(self.lat, self.elevation_m)
+    }
+
+    /// Returns the latitude in `Decimal Degrees`
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    /// Returns the longitude in `Decimal Degrees`
+    pub fn long(&self) -> f64 {
+        self.long
+    }
+
+    /// Returns the `(sin, cos)` of the latitude
+    pub fn lat_sin_cos(&self) -> (f64, f64) {
+        (self.sin_lat, self.cos_lat)
+    }
+
+    /// Returns the AltAz of a celestial body seen from this location.
+    /// This gives the same result as the `AltAzBuilder` without computing the latitude trigonometry again
+    ///
+    /// # Arguments
+    /// * `ra`: Right ascension in | `Decimal Degrees`
+    /// * `dec`: Declination in | `Decimal Degrees`
+    /// * `lst`: Local mean sidereal time in | `Decimal Degrees`
+    pub fn altaz(&self, ra: f64, dec: f64, lst: f64) -> AltAz {
+        AltAz::compute(
+            dec.to_radians(),
+            self.lat.to_radians(),
+            (self.sin_lat, self.cos_lat),
+            lst.to_radians(),
+            ra.to_radians(),
+        )
+    }
+}
+
+/// Equatorial radius of the Earth in `Meters` (WGS84)
+const EQUATORIAL_RADIUS_M: f64 = 6378137.0;
+/// Ratio of the polar to the equatorial radius of the Earth, `1 - f` (WGS84)
+const POLAR_AXIS_RATIO: f64 = 1.0 - 1.0 / 298.257223563;
+
+/// Converts a geodetic latitude, the one of maps and GPS receivers, to the geocentric latitude, the angle at the center
+/// of the Earth between the equator and the observer. The flattening of the Earth (WGS84) makes them differ by up to
+/// about 11.5′ around ±45°, and they agree at the equator and the poles (Meeus, Chapter 11)
+///
+/// # Arguments
+/// * `geodetic_lat`: Geodetic latitude in | `Decimal Degrees`
+/// * `elevation_m`: Height above sea level in | `Meters`
+///
+/// # Example
+/// ```
+/// use astronav::coords::observer::geodetic_to_geocentric_latitude;
+///
+/// let geocentric = geodetic_to_geocentric_latitude(45.0, 0.0);
+///
+/// assert!((45.0 - geocentric - 0.1924).abs() < 1e-3);
+/// ```
+pub fn geodetic_to_geocentric_latitude(geodetic_lat: f64, elevation_m: f64) -> f64 {
+    let (rho_sin, rho_cos) = geocentric_sin_cos(geodetic_lat, elevation_m);
+    rho_sin.atan2(rho_cos).to_degrees()
+}
+
+/// `(ρ sin φ', ρ cos φ')` of an observer, see `ObserverLocation::geocentric_sin_cos`
+fn geocentric_sin_cos(geodetic_lat: f64, elevation_m: f64) -> (f64, f64) {
+    let lat = geodetic_lat.to_radians();
+    let u = (POLAR_AXIS_RATIO * lat.tan()).atan();
+    let height = elevation_m / EQUATORIAL_RADIUS_M;
+
+    (
+        POLAR_AXIS_RATIO * u.sin() + height * lat.sin(),
+        u.cos() + height * lat.cos(),
+    )
+}
+
+/// Returns the altitude in `Decimal Degrees` of the celestial pole visible from a latitude, which is the
+/// North celestial pole in the northern hemisphere and the South celestial pole in the southern one.
+/// Either way the altitude equals the magnitude of the latitude
+pub fn celestial_pole_altitude(lat: f64) -> f64 {
+    lat.abs()
+}
+
+/// Returns the error in `Decimal Degrees` of a polar alignment, as the measured altitude of the mount's polar axis
+/// minus the altitude of the visible celestial pole. A positive error means the axis points too high
+pub fn verify_polar_alignment(measured_pole_alt: f64, lat: f64) -> f64 {
+    measured_pole_alt - celestial_pole_altitude(lat)
+}
@@ -0,0 +1,83 @@
+//! Rise and set times of any celestial body by searching its altitude
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! The closed-form rise and set formulas of the `sun` and `noaa_sun` modules take the position of the Sun once for the day.
+//! The solver here recomputes the position of the body at every step instead, so it serves the Sun, the Moon,
+//! which moves about 13° a day against the stars, and the stars alike.
+//!
+//! # Example
+//! Rise and set of Sirius seen from Chennai on May 17th 2024
+//! ```
+//! use astronav::coords::observer::ObserverLocation;
+//! use astronav::coords::rise_set::rise_set_iterative;
+//! use astronav::time::AstroTime;
+//!
+//! let chennai = ObserverLocation::new(13.0843, 80.2705);
+//! let date = AstroTime::new(17, 5, 2024, 0, 0, 0, 5.5);
+//!
+//! let (rise, set) = rise_set_iterative(|_| (101.2872, -16.7161), -0.5667, &date, &chennai);
+//!
+//! assert_eq!((9, 25), rise.map(|t| (t.hour, t.min)).unwrap());
+//! assert_eq!((20, 55), set.map(|t| (t.hour, t.min)).unwrap());
+//! ```
+
+use super::math::find_root;
+use super::observer::ObserverLocation;
+use crate::time::AstroTime;
+
+/// Minutes between the samples of the altitude, short enough that a body cannot rise and set in between
+const STEP_MINS: i64 = 10;
+
+/// Finds the first rise and the first set of a body during the local day of `date`, as the instants at which its
+/// altitude crosses `threshold` going up and going down, to the second. Either is None if it does not happen that day.
+///
+/// # Arguments
+/// * `position`: Returns the `(Right Ascension, Declination)` of the body in | `Decimal Degrees` at a given instant
+/// * `threshold`: Altitude of the center of the body at the rise and set in | `Decimal Degrees`,
+///   for example `-0.8333` for the Sun, `0.125` for the Moon and `-0.5667` for a star
+/// * `date`: Any time of the day of interest. The day runs from midnight to midnight in the timezone of `date`
+/// * `observer`: Location of the observer
+///
+/// # Returns
+/// The `(rise, set)` instants, in the timezone of `date`
+pub fn rise_set_iterative<F>(
+    position: F,
+    threshold: f64,
+    date: &AstroTime,
+    observer: &ObserverLocation,
+) -> (Option<AstroTime>, Option<AstroTime>)
+where
+    F: Fn(&AstroTime) -> (f64, f64),
+{
+    let midnight = AstroTime::new(date.day, date.month, date.year, 0, 0, 0, date.timezone);
+    let height = |secs: i64| {
+        let t = midnight.plus_seconds(secs);
+        let (ra, dec) = position(&t);
+        observer.altaz(ra, dec, t.lmst_in_degrees(observer.long())).get_altitude() - threshold
+    };
+
+    let (mut rise, mut set) = (None, None);
+    let mut before = height(0);
+
+    for step in 1..=(1440 / STEP_MINS) {
+        let (lo, hi) = ((step - 1) * STEP_MINS * 60, step * STEP_MINS * 60);
+        let after = height(hi);
+
+        if before <= 0.0 && after > 0.0 && rise.is_none() {
+            rise = Some(midnight.plus_seconds(crossing_second(&height, lo, hi)));
+        } else if before > 0.0 && after <= 0.0 && set.is_none() {
+            set = Some(midnight.plus_seconds(crossing_second(&height, lo, hi)));
+        }
+
+        before = after;
+    }
+
+    (rise, set)
+}
+
+/// Finds the second between `lo` and `hi` at which `height` changes sign
+fn crossing_second(height: &impl Fn(i64) -> f64, lo: i64, hi: i64) -> i64 {
+    let height_at = |secs: f64| height(secs.round() as i64);
+
+    find_root(height_at, lo as f64, hi as f64, 0.5).map_or(hi, |secs| secs.round() as i64)
+}
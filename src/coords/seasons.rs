@@ -0,0 +1,94 @@
+//! Find the equinoxes and the solstices, and the season of a date
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! # Example
+//! The June solstice of 2024 fell on June 20th, in Universal Time
+//! ```
+//! use astronav::coords::seasons::{equinoxes_and_solstices, season, Hemisphere, Season};
+//!
+//! let june_solstice = &equinoxes_and_solstices(2024)[1];
+//! assert_eq!((6, 20), (june_solstice.month, june_solstice.day));
+//!
+//! // June 25th 2024 is day 177
+//! assert_eq!(Season::Summer, season(2024, 177, Hemisphere::Northern));
+//! assert_eq!(Season::Winter, season(2024, 177, Hemisphere::Southern));
+//! ```
+
+use crate::time::AstroTime;
+
+/// The half of the Earth an observer is on, split by the equator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+impl Hemisphere {
+    /// Returns the hemisphere of a latitude in `Decimal Degrees`, the equator counting as northern
+    pub fn of_latitude(lat: f64) -> Self {
+        if lat < 0.0 {
+            Hemisphere::Southern
+        } else {
+            Hemisphere::Northern
+        }
+    }
+}
+
+/// The astronomical seasons, each running from an equinox or a solstice to the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// Coefficients of the mean March equinox, June solstice, September equinox and December solstice
+/// as polynomials of the millennia from 2000 (Meeus, Table 27.B)
+#[allow(clippy::excessive_precision)]
+const MEAN_EVENTS: [[f64; 5]; 4] = [
+    [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+    [2451716.56767, 365241.62603, 0.00325, 0.00888, -0.00030],
+    [2451810.21715, 365242.01767, -0.11575, 0.00337, 0.00078],
+    [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+];
+
+/// Returns the instants of the March equinox, the June solstice, the September equinox and the December solstice
+/// of a year, in that order, in Universal Time. These are the mean instants, within half an hour of the true ones,
+/// and are meant for the years 1000 to 3000
+pub fn equinoxes_and_solstices(year: u16) -> [AstroTime; 4] {
+    let y = (year as f64 - 2000.0) / 1000.0;
+    let new_year = AstroTime::new(1, 1, year, 0, 0, 0, 0.0);
+
+    MEAN_EVENTS.map(|c| {
+        let jde = c[0] + y * (c[1] + y * (c[2] + y * (c[3] + y * c[4])));
+        new_year.plus_seconds(((jde - new_year.julian_time_tt()) * 86400.0).round() as i64)
+    })
+}
+
+/// Returns the astronomical season of a day of the year for a hemisphere. A season starts on the day (in Universal Time)
+/// of its equinox or solstice, and those of the southern hemisphere are the opposite of the northern ones
+///
+/// # Arguments
+/// * `year`: Year of interest
+/// * `doy`: Day of the year, starting at 1 for January 1st
+/// * `hemisphere`: Hemisphere of the observer
+pub fn season(year: u16, doy: u16, hemisphere: Hemisphere) -> Season {
+    let starts = equinoxes_and_solstices(year).map(|t| t.day_of_year());
+    let northern = match starts.iter().filter(|start| doy >= **start).count() {
+        1 => Season::Spring,
+        2 => Season::Summer,
+        3 => Season::Autumn,
+        _ => Season::Winter,
+    };
+
+    match hemisphere {
+        Hemisphere::Northern => northern,
+        Hemisphere::Southern => match northern {
+            Season::Spring => Season::Autumn,
+            Season::Summer => Season::Winter,
+            Season::Autumn => Season::Spring,
+            Season::Winter => Season::Summer,
+        },
+    }
+}
@@ -0,0 +1,107 @@
+//! A small built-in catalog of the brightest stars
+// Copyright (c) 2024 Venkatesh Omkaram
+//!
+//! The positions are for the epoch J2000.0 and are kept as Hours Minutes Seconds and Degrees Minutes Seconds,
+//! the way they are printed in star atlases, so they can be passed to `hms_to_deg` and `dms_to_deg`.
+//!
+//! # Example
+//! ```
+//! use astronav::coords::star::catalog::brighter_than;
+//!
+//! let names: Vec<&str> = brighter_than(0.0).iter().map(|s| s.name).collect();
+//!
+//! assert_eq!(vec!["Sirius", "Canopus", "Arcturus", "Rigil Kentaurus"], names);
+//! ```
+
+use crate::coords::{
+    dms_to_deg,
+    frames::{angular_separation, equatorial_to_galactic},
+    hms_to_deg,
+};
+
+/// A star of the built-in catalog
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NamedStar {
+    /// Proper name of the star
+    pub name: &'static str,
+    /// Right Ascension (J2000.0) in format *| "HH:MM:SS"*
+    pub ra: &'static str,
+    /// Declination (J2000.0) in format *| "DD:MM:SS"*
+    pub dec: &'static str,
+    /// Apparent visual magnitude, smaller is brighter
+    pub magnitude: f32,
+    /// Spectral type and luminosity class
+    pub spectral_type: &'static str,
+}
+
+impl NamedStar {
+    /// Returns the Right Ascension in `Decimal Degrees`
+    pub fn ra_in_deg(&self) -> f64 {
+        hms_to_deg(self.ra).expect("catalog Right Ascensions are valid HMS strings")
+    }
+
+    /// Returns the Declination in `Decimal Degrees`
+    pub fn dec_in_deg(&self) -> f64 {
+        dms_to_deg(self.dec).expect("catalog Declinations are valid DMS strings")
+    }
+
+    /// Returns the galactic `(longitude, latitude)` of the star in `Decimal Degrees`
+    pub fn galactic(&self) -> (f64, f64) {
+        equatorial_to_galactic(self.ra_in_deg(), self.dec_in_deg())
+    }
+}
+
+const fn star(
+    name: &'static str,
+    ra: &'static str,
+    dec: &'static str,
+    magnitude: f32,
+    spectral_type: &'static str,
+) -> NamedStar {
+    NamedStar { name, ra, dec, magnitude, spectral_type }
+}
+
+/// The brightest stars of the night sky and Polaris, ordered by magnitude
+pub const BRIGHT_STARS: [NamedStar; 26] = [
+    star("Sirius", "6:45:8.9", "-16:42:58", -1.46, "A1V"),
+    star("Canopus", "6:23:57.1", "-52:41:44", -0.74, "A9II"),
+    star("Arcturus", "14:15:39.7", "19:10:57", -0.05, "K1.5III"),
+    star("Rigil Kentaurus", "14:39:36.5", "-60:50:2", -0.01, "G2V"),
+    star("Vega", "18:36:56.3", "38:47:1", 0.03, "A0V"),
+    star("Capella", "5:16:41.4", "45:59:53", 0.08, "G8III"),
+    star("Rigel", "5:14:32.3", "-8:12:6", 0.13, "B8Ia"),
+    star("Procyon", "7:39:18.1", "5:13:30", 0.34, "F5IV-V"),
+    star("Achernar", "1:37:42.8", "-57:14:12", 0.46, "B6Vep"),
+    star("Betelgeuse", "5:55:10.3", "7:24:25", 0.50, "M1-2Ia"),
+    star("Hadar", "14:3:49.4", "-60:22:23", 0.61, "B1III"),
+    star("Acrux", "12:26:35.9", "-63:5:57", 0.76, "B0.5IV"),
+    star("Altair", "19:50:47.0", "8:52:6", 0.77, "A7V"),
+    star("Aldebaran", "4:35:55.2", "16:30:33", 0.86, "K5III"),
+    star("Antares", "16:29:24.4", "-26:25:55", 0.96, "M1.5Iab"),
+    star("Spica", "13:25:11.6", "-11:9:41", 0.97, "B1V"),
+    star("Pollux", "7:45:18.9", "28:1:34", 1.14, "K0III"),
+    star("Fomalhaut", "22:57:39.0", "-29:37:20", 1.16, "A3V"),
+    star("Deneb", "20:41:25.9", "45:16:49", 1.25, "A2Ia"),
+    star("Mimosa", "12:47:43.3", "-59:41:19", 1.25, "B0.5III"),
+    star("Regulus", "10:8:22.3", "11:58:2", 1.35, "B8IVn"),
+    star("Adhara", "6:58:37.5", "-28:58:20", 1.50, "B2II"),
+    star("Castor", "7:34:36.0", "31:53:18", 1.58, "A1V"),
+    star("Shaula", "17:33:36.5", "-37:6:14", 1.62, "B2IV"),
+    star("Bellatrix", "5:25:7.9", "6:20:59", 1.64, "B2III"),
+    star("Polaris", "2:31:49.1", "89:15:51", 1.98, "F7Ib"),
+];
+
+/// Returns the catalog stars brighter than (with a magnitude below) `mag`, brightest first
+pub fn brighter_than(mag: f32) -> Vec<NamedStar> {
+    BRIGHT_STARS.iter().filter(|s| s.magnitude < mag).copied().collect()
+}
+
+/// Returns the catalog star closest to the position `(ra, dec)` in `Decimal Degrees`,
+/// together with its angular separation from that position in `Decimal Degrees`
+pub fn nearest_catalog_star(ra: f64, dec: f64) -> (NamedStar, f64) {
+    BRIGHT_STARS
+        .iter()
+        .map(|s| (*s, angular_separation(ra, dec, s.ra_in_deg(), s.dec_in_deg())))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("the catalog is not empty")
+}
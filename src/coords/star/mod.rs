@@ -44,9 +44,39 @@
 //! assert_eq!(30.10106212143597, alt.get_altitude());
 //! assert_eq!(130.98870686438966, alt.get_azimuth());
 //! ```
+pub mod catalog;
+
+use std::f64::consts::PI;
+use std::fmt;
 use std::marker::PhantomData;
 
+use super::frames::angular_separation;
 use super::struct_types::*;
+use super::{dms_to_deg, hms_to_deg, refraction_in_deg, CoordParseError};
+use crate::time::AstroTime;
+
+/// Below this cosine the observer or the object is treated as being exactly at the pole or the zenith
+const DEGENERATE_COS: f64 = 1e-6;
+
+/// Geometries in which the Azimuth of a celestial body is not defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AltAzError {
+    /// The observer is at the North or South Pole, where every direction is South or North respectively
+    ObserverAtPole,
+    /// The object is at the zenith or the nadir of the observer
+    ObjectAtZenith,
+}
+
+impl fmt::Display for AltAzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AltAzError::ObserverAtPole => write!(f, "azimuth is undefined for an observer at a pole"),
+            AltAzError::ObjectAtZenith => write!(f, "azimuth is undefined for an object at the zenith or nadir"),
+        }
+    }
+}
+
+impl std::error::Error for AltAzError {}
 
 /// A safe way to find the Altitude and Azimuth of a given Star
 #[allow(unused)]
@@ -66,23 +96,95 @@ impl AltAz {
         self.alt.to_degrees()
     }
 
-    /// Returns the Azimuth of a celestial body in `Decimal Degrees`
+    /// Returns the Hour Angle of a celestial body in `Decimal Degrees` within `(-180, 180]`,
+    /// positive west of the meridian and negative east of it
+    pub fn get_hour_angle(&self) -> f64 {
+        self.ha.to_degrees()
+    }
+
+    /// Returns the Azimuth of a celestial body in `Decimal Degrees`.
+    ///
+    /// The Azimuth is not defined for an observer at a pole or for an object at the zenith or nadir.
+    /// In those cases this returns `0.0`, use `try_get_azimuth` to tell them apart from an object due North
     pub fn get_azimuth(&self) -> f64 {
-        let alt_tup = self.alt.sin_cos();
+        self.try_get_azimuth().unwrap_or(0.0)
+    }
+
+    /// Returns true if the altitude and the azimuth of both positions are within `tol_deg` degrees of each other.
+    /// The azimuths are compared the short way round, so 359.9° and 0.1° are 0.2° apart
+    pub fn is_close(&self, other: &AltAz, tol_deg: f64) -> bool {
+        let d_az = (self.get_azimuth() - other.get_azimuth()).rem_euclid(360.0);
+
+        (self.get_altitude() - other.get_altitude()).abs() <= tol_deg && d_az.min(360.0 - d_az) <= tol_deg
+    }
+
+    /// Returns the parallactic angle of a celestial body in `Decimal Degrees`, see `parallactic_angle`
+    pub fn get_parallactic_angle(&self) -> f64 {
+        parallactic_angle(self.get_hour_angle(), self.dec.to_degrees(), self.lat.to_degrees())
+    }
+
+    /// Returns the Azimuth of a celestial body in `Decimal Degrees`, or an `AltAzError` when the geometry leaves it undefined
+    pub fn try_get_azimuth(&self) -> Result<f64, AltAzError> {
+        self.azimuth_for_altitude(self.alt)
+    }
+
+    /// Returns the apparent Altitude of a celestial body in `Decimal Degrees`, which is the
+    /// geometric altitude raised by atmospheric refraction (Bennett)
+    pub fn get_apparent_altitude(&self) -> f64 {
+        let alt = self.get_altitude();
+        alt + refraction_in_deg(alt)
+    }
+
+    /// Returns the Azimuth in `Decimal Degrees` recomputed with the apparent Altitude, so that it stays consistent
+    /// with `get_apparent_altitude` when pointing close to the horizon. Falls back to `0.0` like `get_azimuth`
+    pub fn get_apparent_azimuth(&self) -> f64 {
+        self.azimuth_for_altitude(self.get_apparent_altitude().to_radians())
+            .unwrap_or(0.0)
+    }
+
+    /// Returns a new position shifted by `d_alt_deg` in Altitude and `d_az_deg` in Azimuth, as a mount nudged by
+    /// a guiding correction would point. The Azimuth wraps into `[0, 360)` and the Altitude is clamped to `[-90, 90]`.
+    /// The Right Ascension and Declination of the result are those of the new pointing, for the same observer and time
+    pub fn offset(&self, d_alt_deg: f64, d_az_deg: f64) -> AltAz {
+        let alt = (self.get_altitude() + d_alt_deg).clamp(-90.0, 90.0).to_radians();
+        let az = (self.get_azimuth() + d_az_deg).rem_euclid(360.0).to_radians();
+
+        let (alt_sin, alt_cos) = alt.sin_cos();
         let lat_tup = self.lat.sin_cos();
+        let dec = (alt_sin * lat_tup.0 + alt_cos * lat_tup.1 * az.cos()).clamp(-1.0, 1.0).asin();
+        let ha = (-az.sin() * alt_cos).atan2(alt_sin * lat_tup.1 - alt_cos * lat_tup.0 * az.cos());
 
+        AltAz::compute(dec, self.lat, lat_tup, self.lst, self.lst - ha)
+    }
+
+    /// Returns the Azimuth in `Decimal Degrees` for the given Altitude in radians
+    fn azimuth_for_altitude(&self, alt: f64) -> Result<f64, AltAzError> {
+        let alt_tup = alt.sin_cos();
+        let lat_tup = self.lat.sin_cos();
+
+        if lat_tup.1.abs() < DEGENERATE_COS {
+            return Err(AltAzError::ObserverAtPole);
+        }
+        if alt_tup.1.abs() < DEGENERATE_COS {
+            return Err(AltAzError::ObjectAtZenith);
+        }
+
+        // Rounding can push the cosine just outside [-1, 1] for objects on the meridian
         let az = ((self.dec.sin() - (alt_tup.0 * lat_tup.0)) / (alt_tup.1 * lat_tup.1))
+            .clamp(-1.0, 1.0)
             .acos()
             .to_degrees();
-        if self.ha.to_degrees() / 15.0 < 12.0 {
-            360.0 - az
+        // East of the meridian the azimuth is measured the other way round
+        if (0.0..PI).contains(&self.ha) {
+            Ok(360.0 - az)
         } else {
-            az
+            Ok(az)
         }
     }
 }
 
-/// Helps to build an AltAz type using a `builder pattern`
+/// Helps to build an AltAz type using a `builder pattern`.
+/// All the setters take `Decimal Degrees`, see `radians_to_degrees` for values in radians
 #[derive(Default, Clone)]
 pub struct AltAzBuilder<U, K, L, M, S> {
     dec: U,
@@ -120,22 +222,19 @@ impl AltAzBuilder<Dec, Lat, Lst, RA, Sealed> {
 
     /// Builds an AltAz type using an AltAzBuilder
     pub fn build(self) -> AltAz {
-        let dec = self.dec.0;
-
         let lat = self.lat.0;
-        let lst = self.lst.0;
-        let ra = self.ra.0;
+        AltAz::compute(self.dec.0, lat, lat.sin_cos(), self.lst.0, self.ra.0)
+    }
+}
 
+impl AltAz {
+    /// Computes the AltAz from angles in radians, taking the `(sin, cos)` of the latitude from the caller
+    pub(crate) fn compute(dec: f64, lat: f64, lat_tup: (f64, f64), lst: f64, ra: f64) -> AltAz {
         let dec_tup = dec.sin_cos();
-        let lat_tup = lat.sin_cos();
-
-        let ha = if lst > ra { lst - ra } else { ra - lst };
 
-        let ha_for_az = if lst.to_degrees() - ra.to_degrees() < 0.0 {
-            (360.0_f64 + (lst.to_degrees() - ra.to_degrees())).to_radians()
-        } else {
-            lst - ra
-        };
+        // The one signed hour angle, positive west of the meridian. The cosine is taken before wrapping it,
+        // as wrapping would shift the last bits of the altitude
+        let ha = lst - ra;
 
         let alt = (dec_tup.0 * lat_tup.0 + dec_tup.1 * lat_tup.1 * ha.cos()).asin();
 
@@ -145,11 +244,189 @@ impl AltAzBuilder<Dec, Lat, Lst, RA, Sealed> {
             lst,
             ra,
             alt,
-            ha: ha_for_az,
+            ha: signed_hour_angle(ha),
         }
     }
 }
 
+/// Returns the angular separation in `Decimal Degrees` between two positions in the horizontal frame of the same observer.
+/// Useful to check whether a planet or a star is far enough from the Sun to be seen or imaged in daylight
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::{sky_separation, AltAzBuilder};
+///
+/// let a = AltAzBuilder::new().dec(10.0).lat(12.45).lmst(100.0).ra(100.0).seal().build();
+/// let b = AltAzBuilder::new().dec(30.0).lat(12.45).lmst(100.0).ra(100.0).seal().build();
+///
+/// // Both on the meridian, so they are apart by their difference in declination
+/// assert!((sky_separation(&a, &b) - 20.0).abs() < 1e-9);
+/// ```
+pub fn sky_separation(a: &AltAz, b: &AltAz) -> f64 {
+    angular_separation(a.get_azimuth(), a.get_altitude(), b.get_azimuth(), b.get_altitude())
+}
+
+/// Returns the hour angle in `Decimal Degrees` within `[0, 180]` at which a body crosses the altitude `alt`.
+/// It crosses going up at minus this hour angle (east of the meridian) and going down at plus it (west of the meridian).
+/// Returns None if the body stays above or below `alt` all day
+///
+/// # Arguments
+/// * `dec`: Declination in | `Decimal Degrees`
+/// * `lat`: Latitude of the observer in | `Decimal Degrees`
+/// * `alt`: Altitude in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::hour_angle_at_altitude;
+///
+/// // On the celestial equator a body is above the horizon for 12 hours
+/// assert!((hour_angle_at_altitude(0.0, 40.0, 0.0).unwrap() - 90.0).abs() < 1e-9);
+/// ```
+pub fn hour_angle_at_altitude(dec: f64, lat: f64, alt: f64) -> Option<f64> {
+    let (dec, lat, alt) = (dec.to_radians(), lat.to_radians(), alt.to_radians());
+    let cos_h = (alt.sin() - dec.sin() * lat.sin()) / (dec.cos() * lat.cos());
+
+    if (-1.0..=1.0).contains(&cos_h) {
+        Some(cos_h.acos().to_degrees())
+    } else {
+        None
+    }
+}
+
+/// Returns the hour angles in `Decimal Degrees` between which a body is above the altitude `min_alt`, as `(-h, h)`
+/// symmetric about its transit. Divide `2h` by 15 and by `SIDEREAL_RATE` for the observable time in solar hours.
+/// A body that stays above `min_alt` all day gives `(-180, 180)`, and None is returned when it never gets there
+///
+/// # Arguments
+/// * `dec`: Declination in | `Decimal Degrees`
+/// * `lat`: Latitude of the observer in | `Decimal Degrees`
+/// * `min_alt`: Lowest useful altitude in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::visibility_ha_range;
+///
+/// // A body on the celestial equator is above the horizon for 12 sidereal hours
+/// let (from, to) = visibility_ha_range(0.0, 40.0, 0.0).unwrap();
+///
+/// assert!((to - from - 180.0).abs() < 1e-9);
+/// ```
+pub fn visibility_ha_range(dec: f64, lat: f64, min_alt: f64) -> Option<(f64, f64)> {
+    if let Some(ha) = hour_angle_at_altitude(dec, lat, min_alt) {
+        return Some((-ha, ha));
+    }
+
+    // Neither crossing, so the body is above or below `min_alt` all day, as it is at its transit
+    if 90.0 - (lat - dec).abs() > min_alt {
+        Some((-180.0, 180.0))
+    } else {
+        None
+    }
+}
+
+/// Returns true if a body never sets below the geometric horizon seen from the latitude `lat`.
+/// A body grazing the horizon at its lower culmination counts as circumpolar
+///
+/// # Arguments
+/// * `dec`: Declination in | `Decimal Degrees`
+/// * `lat`: Latitude of the observer in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::is_circumpolar;
+///
+/// // Polaris never sets from Europe, and is not seen at all from Australia
+/// assert!(is_circumpolar(89.26, 48.0));
+/// assert!(!is_circumpolar(89.26, -33.0));
+/// ```
+pub fn is_circumpolar(dec: f64, lat: f64) -> bool {
+    // Altitude of the lower culmination
+    lat.abs() - 90.0 + dec * lat.signum() >= 0.0
+}
+
+/// Returns the latitude in `Decimal Degrees` from which a body starts being circumpolar, `90 - |dec|`, on the
+/// hemisphere of the body: positive for a body north of the celestial equator and negative for a body south of it.
+/// The body is circumpolar from that latitude to the pole of its hemisphere, see `is_circumpolar`
+///
+/// # Arguments
+/// * `dec`: Declination in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::circumpolar_latitude;
+///
+/// // Acrux never sets south of latitude 27°S
+/// assert!((circumpolar_latitude(-63.1) - -26.9).abs() < 1e-9);
+/// ```
+pub fn circumpolar_latitude(dec: f64) -> f64 {
+    (90.0 - dec.abs()).copysign(dec)
+}
+
+/// Returns the parallactic angle in `Decimal Degrees` within `(-180, 180]`, the angle at the body between the direction
+/// to the celestial pole and the direction to the zenith. It is negative east of the meridian and positive west of it,
+/// and passes through 0 on the meridian, or through 180 for a body culminating between the zenith and the pole.
+/// Its change over time is the field rotation seen by an alt-azimuth mount
+///
+/// # Arguments
+/// * `ha`: Hour angle in | `Decimal Degrees` (positive west of the meridian)
+/// * `dec`: Declination in | `Decimal Degrees`
+/// * `lat`: Latitude of the observer in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::parallactic_angle;
+///
+/// assert_eq!(0.0, parallactic_angle(0.0, 20.0, 40.0));
+/// assert!(parallactic_angle(30.0, 20.0, 40.0) > 0.0);
+/// ```
+pub fn parallactic_angle(ha: f64, dec: f64, lat: f64) -> f64 {
+    let (ha, dec, lat) = (ha.to_radians(), dec.to_radians(), lat.to_radians());
+
+    ha.sin()
+        .atan2(lat.tan() * dec.cos() - dec.sin() * ha.cos())
+        .to_degrees()
+}
+
+/// Returns the hour angle of a body in `Decimal Degrees` within `(-180, 180]`, positive west of the meridian and negative
+/// east of it, like `AltAz::get_hour_angle` but without computing the altitude. Enough to decide on a meridian flip
+///
+/// # Arguments
+/// * `ra`: Right ascension in | `Decimal Degrees`
+/// * `longitude`: Longitude of the observer in | `Decimal Degrees` (+ east, - west)
+/// * `time`: The instant, whose local mean sidereal time is used
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::hour_angle_of_star;
+/// use astronav::time::AstroTime;
+///
+/// let time = AstroTime::new(17, 5, 2024, 21, 0, 0, 5.5);
+/// let lst = time.lmst_in_degrees(80.2705);
+///
+/// // An hour (15°) after crossing the meridian
+/// assert!((hour_angle_of_star(lst - 15.0, 80.2705, &time) - 15.0).abs() < 1e-9);
+/// ```
+pub fn hour_angle_of_star(ra: f64, longitude: f64, time: &AstroTime) -> f64 {
+    let ha = (time.lmst_in_degrees(longitude) - ra).rem_euclid(360.0);
+
+    if ha > 180.0 {
+        ha - 360.0
+    } else {
+        ha
+    }
+}
+
+/// Wraps an hour angle in radians into `(-PI, PI]`
+fn signed_hour_angle(ha: f64) -> f64 {
+    if ha > PI {
+        ha - 2.0 * PI
+    } else if ha <= -PI {
+        ha + 2.0 * PI
+    } else {
+        ha
+    }
+}
+
 impl<U, K, L, M, S> AltAzBuilder<U, K, L, M, S> {
 
     /// Sets the declination angle in `Decimal Degrees` and returns the AltAzBuilder
@@ -199,4 +476,192 @@ impl<U, K, L, M, S> AltAzBuilder<U, K, L, M, S> {
             marker_seal: PhantomData::<NotSealed>,
         }
     }
+
+    /// Sets the declination from Degrees Minutes Seconds in format `"DD:MM:SS"` and returns the AltAzBuilder,
+    /// or a `CoordParseError` if the string can not be parsed
+    pub fn dec_dms(self, dms: &str) -> Result<AltAzBuilder<Dec, K, L, M, NotSealed>, CoordParseError> {
+        Ok(self.dec(dms_to_deg(dms)?))
+    }
+
+    /// Sets the right ascension from Hours Minutes Seconds in format `"HH:MM:SS"` and returns the AltAzBuilder,
+    /// or a `CoordParseError` if the string can not be parsed
+    pub fn ra_hms(self, hms: &str) -> Result<AltAzBuilder<U, K, L, RA, NotSealed>, CoordParseError> {
+        Ok(self.ra(hms_to_deg(hms)?))
+    }
+}
+
+/// Returns true if a star rises above `min_altitude` while the sky is astronomically dark
+/// (the Sun is more than 18° below the horizon) on the night following the given date.
+///
+/// The night is sampled every 10 minutes from local noon of the given date to local noon of the next day.
+/// The position of the Sun is computed using the NOAA algorithms from the `noaa_sun` module.
+///
+/// # Arguments
+/// * `ra`: Right ascension of the star in | `Decimal Degrees`
+/// * `dec`: Declination of the star in | `Decimal Degrees`
+/// * `lat`: Latitude of the observer in | `Decimal Degrees` (+ north, - south)
+/// * `long`: Longitude of the observer in | `Decimal Degrees` (+ east, - west)
+/// * `date`: Local date of the evening as `(year, month, day)`
+/// * `min_altitude`: Lowest useful altitude of the star in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::is_observable_tonight;
+///
+/// // Betelgeuse from New York on a January night
+/// assert!(is_observable_tonight(88.7929, 7.4071, 40.7128, -74.0060, (2024, 1, 15), 20.0));
+/// ```
+#[cfg(feature = "noaa-sun")]
+#[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
+pub fn is_observable_tonight(
+    ra: f64,
+    dec: f64,
+    lat: f64,
+    long: f64,
+    date: (u16, u8, u8),
+    min_altitude: f64,
+) -> bool {
+    use super::noaa_sun::NOAASun;
+    use crate::time::{
+        day_of_year, gmst_in_degrees, is_leap_year, julian_day_number, julian_time, lmst_in_degrees,
+    };
+
+    const ASTRONOMICAL_DARKNESS: f64 = -18.0;
+    const STEP_MINS: u32 = 10;
+
+    let (year, month, day) = date;
+    // Local mean time keeps the local midnight in the middle of the sampled span
+    let timezone = (long / 15.0) as f32;
+    let jd = julian_day_number(day, month, year);
+    let doy = day_of_year(year, month, day);
+
+    (0..=(1440 / STEP_MINS))
+        .map(|step| 720 + step * STEP_MINS)
+        .any(|mins| {
+            let next_day = (mins / 1440) as u16;
+            let hour = ((mins % 1440) / 60) as u8;
+            let min = (mins % 60) as u8;
+
+            let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+            let (sun_year, sun_doy) = if doy + next_day > days_in_year {
+                (year + 1, doy + next_day - days_in_year)
+            } else {
+                (year, doy + next_day)
+            };
+
+            let sun = NOAASun {
+                year: sun_year,
+                doy: sun_doy,
+                long: long as f32,
+                lat: lat as f32,
+                timezone,
+                hour,
+                min,
+                sec: 0,
+                millis: 0,
+            };
+
+            if sun.altitude_in_deg() > ASTRONOMICAL_DARKNESS {
+                return false;
+            }
+
+            let jt = julian_time(jd + next_day as u32, hour, min, 0, timezone);
+            let lmst = lmst_in_degrees(gmst_in_degrees(jt), long);
+
+            let alt = AltAzBuilder::new()
+                .dec(dec)
+                .lat(lat)
+                .lmst(lmst)
+                .ra(ra)
+                .seal()
+                .build()
+                .get_altitude();
+
+            alt >= min_altitude
+        })
+}
+
+/// Returns the day of the year of the heliacal rising of a star, the first morning it is seen low in the east
+/// in the twilight before sunrise after weeks hidden in the glare of the Sun. Returns None if that does not
+/// happen within the year, for example when the star is circumpolar or never rises, and for the year 0,
+/// as the search starts from the last day of the year before.
+///
+/// A star counts as seen when it is at least 1° above the horizon as the Sun reaches the arcus visionis,
+/// a depression below the horizon that grows for fainter stars, taken here as `11° + magnitude` within `[7°, 16°]`.
+/// The position of the Sun is computed using the NOAA algorithms from the `noaa_sun` module.
+///
+/// # Arguments
+/// * `ra`: Right ascension of the star in | `Decimal Degrees`
+/// * `dec`: Declination of the star in | `Decimal Degrees`
+/// * `magnitude`: Visual magnitude of the star
+/// * `lat`: Latitude of the observer in | `Decimal Degrees` (+ north, - south)
+/// * `long`: Longitude of the observer in | `Decimal Degrees` (+ east, - west)
+/// * `year`: Year to search
+///
+/// # Example
+/// ```
+/// use astronav::coords::star::heliacal_rising_doy;
+///
+/// // Sirius from Alexandria, in the summer
+/// let doy = heliacal_rising_doy(101.2872, -16.7161, -1.46, 31.2001, 29.9187, 2024).unwrap();
+/// assert!((182..244).contains(&doy));
+/// ```
+#[cfg(feature = "noaa-sun")]
+#[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
+pub fn heliacal_rising_doy(ra: f64, dec: f64, magnitude: f32, lat: f64, long: f64, year: u16) -> Option<u16> {
+    use super::noaa_sun::NOAASun;
+    use crate::time::{day_of_year_to_date, gmst_in_degrees, is_leap_year, julian_day_number, julian_time, lmst_in_degrees};
+
+    const MIN_STAR_ALTITUDE: f64 = 1.0;
+
+    let arcus_visionis = (11.0 + magnitude as f64).clamp(7.0, 16.0);
+    // Local mean time keeps the morning twilight well inside the local date
+    let timezone = (long / 15.0) as f32;
+
+    let seen_on = |year: u16, doy: u16| {
+        let sun = NOAASun {
+            year,
+            doy,
+            long: long as f32,
+            lat: lat as f32,
+            timezone,
+            hour: 6,
+            min: 0,
+            sec: 0,
+            millis: 0,
+        };
+        let Some(mins) = sun.morning_time_mins_by_zenith(90.0 + arcus_visionis) else {
+            return false;
+        };
+
+        let (month, day) = day_of_year_to_date(year, doy);
+        let jt = julian_time(julian_day_number(day, month, year), 0, 0, 0, timezone) + mins / 1440.0;
+        let lmst = lmst_in_degrees(gmst_in_degrees(jt), long);
+
+        let alt = AltAzBuilder::new()
+            .dec(dec)
+            .lat(lat)
+            .lmst(lmst)
+            .ra(ra)
+            .seal()
+            .build()
+            .get_altitude();
+
+        alt >= MIN_STAR_ALTITUDE
+    };
+
+    let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+    let year_before = year.checked_sub(1)?;
+    let last_day_before = if is_leap_year(year_before) { 366 } else { 365 };
+    let mut seen_before = seen_on(year_before, last_day_before);
+
+    for doy in 1..=days_in_year {
+        let seen = seen_on(year, doy);
+        if seen && !seen_before {
+            return Some(doy);
+        }
+        seen_before = seen;
+    }
+
+    None
 }
@@ -4,11 +4,39 @@
 // Sunrise/sunset calculation in squirrel, hugo@electricimp.com
 // Based on http://williams.best.vwh.net/sunrise_sunset_algorithm.htm
 
-use std::f32::consts::PI;
+use super::{validate_latitude, wrap_longitude, InvalidLatitude};
+use crate::time::{day_of_year, day_of_year_to_date, julian_centuries, AstroTime};
+#[cfg(feature = "noaa-sun")]
+use crate::time::{mean_obliquity_in_degrees, nutation_in_degrees};
+
+/// The point of the Sun's disc that marks its rise and set on the horizon
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimbReference {
+    /// The top of the disc touches the horizon, the standard definition, with the center 0.833° below the horizon
+    #[default]
+    UpperLimb,
+    /// The center of the disc is on the horizon, with the center 0.567° below the horizon allowing for refraction only
+    Center,
+    /// The bottom of the disc touches the horizon, with the center 0.301° below the horizon
+    LowerLimb,
+}
 
-use crate::time::day_of_year;
+impl LimbReference {
+    /// Returns the zenith angle of the Sun's center in degrees at the rise and set. It can be passed to
+    /// `NOAASun::morning_time_mins_by_zenith` and `NOAASun::evening_time_mins_by_zenith`
+    pub fn zenith_in_deg(&self) -> f64 {
+        match self {
+            LimbReference::UpperLimb => 90.833,
+            LimbReference::Center => 90.567,
+            LimbReference::LowerLimb => 90.301,
+        }
+    }
 
-const ZENITH: f32 = 90.833;
+    /// `zenith_in_deg` rounded once from the decimal value, for the f32 computations
+    fn zenith_f32(&self) -> f32 {
+        self.zenith_in_deg() as f32
+    }
+}
 
 // An enum only related to the SunRiseAndSet Struct
 #[derive(Debug)]
@@ -33,6 +61,7 @@ pub enum SunMood {
 ///     long: -74.0060,
 ///     lat: 40.7128,
 ///     timezone: -4.0,
+///     ..Default::default()
 /// };
 ///
 /// let sma = sun_new_york.sunrise_mean_anomaly();
@@ -63,6 +92,7 @@ pub enum SunMood {
 ///     long: -74.0060,
 ///     lat: 40.7128,
 ///     timezone: -4.0,
+///     ..Default::default()
 /// };
 ///
 /// let sma = sun_new_york.sunset_mean_anomaly();
@@ -121,6 +151,16 @@ pub struct SunRiseAndSet {
     pub lat: f32,
     /// Timezone of the point of interest in hours (+ east, - west)
     pub timezone: f32,
+    /// Year of interest, also set by `date`. Only used by the accurate model
+    #[cfg(feature = "noaa-sun")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
+    pub year: Option<u16>,
+    /// Use the declination by Meeus and the equation of time by W. M. Smart, see `with_accurate_model`
+    #[cfg(feature = "noaa-sun")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
+    pub accurate: bool,
+    /// The point of the Sun's disc the rise and set are taken for, the upper limb by default
+    pub limb: LimbReference,
 }
 
 impl SunRiseAndSet {
@@ -131,98 +171,181 @@ impl SunRiseAndSet {
 
     pub fn date(self, year: u16, month: u8, day: u8) -> Self {
         let doy = day_of_year(year, month, day);
-        Self { doy, ..self }
+        Self {
+            doy,
+            #[cfg(feature = "noaa-sun")]
+            year: Some(year),
+            ..self
+        }
+    }
+
+    /// Sets the point of the Sun's disc the rise and set are taken for
+    pub fn limb(self, limb: LimbReference) -> Self {
+        Self { limb, ..self }
+    }
+
+    /// Switches to the apparent declination of the Sun by Meeus (Chapter 25) and the equation of time by W. M. Smart,
+    /// which bring the rise and set to within a few seconds of a full computation instead of the 10 to 25 seconds of the
+    /// default model. All the methods keep their names, but the mean anomaly, true longitude and right ascension are
+    /// no longer used for the times. The accurate model needs the year, so it only takes effect once `date` (or the
+    /// `year` field) has set it; with the day of the year alone the default model is used
+    #[cfg(feature = "noaa-sun")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "noaa-sun")))]
+    pub fn with_accurate_model(self) -> Self {
+        Self { accurate: true, ..self }
+    }
+
+    /// Returns the local clock time of the day for the accurate model, or None when the default model is in use
+    #[cfg(feature = "noaa-sun")]
+    fn accurate_time(&self, hour: u8, min: u8) -> Option<AstroTime> {
+        let year = self.year.filter(|_| self.accurate)?;
+        let (month, day) = day_of_year_to_date(year, self.doy);
+        Some(AstroTime::new(day, month, year, hour, min, 0, self.timezone))
+    }
+
+    /// Whether the accurate model is in use: it is set and the year is known
+    #[cfg(feature = "noaa-sun")]
+    fn is_accurate(&self) -> bool {
+        self.accurate_time(0, 0).is_some()
     }
 
+    #[cfg(not(feature = "noaa-sun"))]
+    fn is_accurate(&self) -> bool {
+        false
+    }
+
+    /// Declination in degrees of the accurate model at the given local hour, or None for the default model
+    #[cfg(feature = "noaa-sun")]
+    fn accurate_declination(&self, hour: u8) -> Option<f32> {
+        Some(apparent_declination(self.accurate_time(hour, 0)?.julian_time_tt()) as f32)
+    }
+
+    #[cfg(not(feature = "noaa-sun"))]
+    fn accurate_declination(&self, _hour: u8) -> Option<f32> {
+        None
+    }
+
+    /// Local mean time in hours of the Sun Rise (or Set) of the accurate model, or None for the default model.
+    /// The Sun's declination and the equation of time are taken again at the first estimate of the event
+    #[cfg(feature = "noaa-sun")]
+    fn accurate_local_mean_time(&self, rising: bool, horizon_altitude_deg: f32) -> Option<Result<f32, SunMood>> {
+        let mut clock_mins: f64 = if rising { 360.0 } else { 1080.0 };
+
+        for _ in 0..2 {
+            let mins = clock_mins.rem_euclid(1440.0) as u16;
+            let time = self.accurate_time((mins / 60) as u8, (mins % 60) as u8)?;
+            let dec = apparent_declination(time.julian_time_tt()) as f32;
+            let ha = match self.horizon_ha_in_deg(dec, horizon_altitude_deg) {
+                Ok(ha) => ha as f64,
+                Err(mood) => return Some(Err(mood)),
+            };
+            let ha = if rising { ha } else { -ha };
+            let eot = super::noaa_sun::NOAASun::from(&time).eot_in_mins();
+
+            clock_mins = 720.0 - 4.0 * (self.long as f64 + ha) - eot + self.timezone as f64 * 60.0;
+        }
+
+        Some(Ok(clock_mins as f32 / 60.0 + self.long / 15.0 - self.timezone))
+    }
+
+    #[cfg(not(feature = "noaa-sun"))]
+    fn accurate_local_mean_time(&self, _rising: bool, _horizon_altitude_deg: f32) -> Option<Result<f32, SunMood>> {
+        None
+    }
+
+    /// Sets the longitude, wrapped into `-180..=180` degrees
     pub fn long(self, long: f32) -> Self {
-        Self { long, ..self }
+        Self { long: wrap_longitude(long), ..self }
     }
 
+    /// Sets the latitude as given. Use `try_lat` to reject a latitude outside `-90..=90` degrees,
+    /// which would otherwise make the computations return `NaN`
     pub fn lat(self, lat: f32) -> Self {
         Self { lat, ..self }
     }
 
+    /// Sets the latitude, or returns an error if it is outside `-90..=90` degrees
+    pub fn try_lat(self, lat: f32) -> Result<Self, InvalidLatitude> {
+        Ok(Self { lat: validate_latitude(lat)?, ..self })
+    }
+
     pub fn timezone(self, timezone: f32) -> Self {
         Self { timezone, ..self }
     }
 
     pub fn sunrise_mean_anomaly(&self) -> f32 {
-        let long_hour = self.long / 15.0;
-
-        let t = self.doy as f32 + ((6.0 - long_hour) / 24.0);
-
-        (0.9856 * t) - 3.289
+        self.mean_anomaly_at(6.0)
     }
 
     pub fn sunset_mean_anomaly(&self) -> f32 {
-        let long_hour = self.long / 15.0;
-
-        let t = self.doy as f32 + ((18.0 - long_hour) / 24.0);
+        self.mean_anomaly_at(18.0)
+    }
 
-        (0.9856 * t) - 3.289
+    /// Mean anomaly of the Sun at the given local hour of the day
+    fn mean_anomaly_at<T: SunRiseFloat>(&self, hour: T) -> T {
+        T::mean_anomaly(self.doy, self.long, hour)
     }
 
     pub fn sunrise_true_long_in_deg(&self) -> f32 {
-        let sma = self.sunrise_mean_anomaly();
-        let mut l = sma
-            + (1.916 * sma.to_radians().sin())
-            + (0.020 * (2.0 * sma).to_radians().sin())
-            + 282.634;
-
-        let stl = if l < 0.0 {
-            l += 360.0;
-            l
-        } else if l > 360.0 {
-            l -= 360.0;
-            l
-        } else {
-            l
-        };
-
-        stl
+        SunRiseFloat::true_long_in_deg(self.sunrise_mean_anomaly())
     }
 
     pub fn sunset_true_long_in_deg(&self) -> f32 {
-        let sma = self.sunset_mean_anomaly();
-        let mut l = sma
-            + (1.916 * sma.to_radians().sin())
-            + (0.020 * (2.0 * sma).to_radians().sin())
-            + 282.634;
-
-        let stl = if l < 0.0 {
-            l += 360.0;
-            l
-        } else if l > 360.0 {
-            l -= 360.0;
-            l
-        } else {
-            l
-        };
-
-        stl
+        SunRiseFloat::true_long_in_deg(self.sunset_mean_anomaly())
     }
 
     pub fn sunrise_declination(&self) -> f32 {
-        let stl = self.sunrise_true_long_in_deg();
-        (0.39782 * stl.to_radians().sin()).asin().to_degrees()
+        self.declination_at(6)
     }
 
     pub fn sunset_declination(&self) -> f32 {
-        let stl = self.sunset_true_long_in_deg();
-        (0.39782 * stl.to_radians().sin()).asin().to_degrees()
+        self.declination_at(18)
     }
 
-    pub fn sunrise_time(&self) -> Result<f32, SunMood> {
-        let lha = self.sunrise_local_ha_in_deg()?;
+    /// Declination of the Sun in degrees at the given local hour of the day
+    fn declination_at(&self, hour: u8) -> f32 {
+        if let Some(dec) = self.accurate_declination(hour) {
+            return dec;
+        }
+
+        SunRiseFloat::declination(f32::true_long_in_deg(self.mean_anomaly_at(hour as f32)))
+    }
+
+    /// Local mean time of the Sun Rise in hours, not wrapped into `0..24`
+    fn sunrise_local_mean_time(&self, horizon_altitude_deg: f32) -> Result<f32, SunMood> {
+        if let Some(t) = self.accurate_local_mean_time(true, horizon_altitude_deg) {
+            return t;
+        }
+
+        let lha = self.sunrise_local_ha_by_horizon(horizon_altitude_deg)?;
+
         let ra = self.sunrise_ra_in_hours();
-        let doy = self.doy;
-        let long = self.long;
-        let local_offset = self.timezone;
-        let long_hour = long / 15.0;
+        let long_hour = self.long / 15.0;
+
+        let t = self.doy as f32 + ((6.0 - long_hour) / 24.0);
+
+        Ok(lha + ra - (0.06571 * t) - 6.622)
+    }
 
-        let t = doy as f32 + ((6.0 - long_hour) / 24.0);
+    /// Sun Rise as a `(year, month, day, hour, min, sec)` date and time in UT for the given year of `doy`.
+    /// Unlike `sunrise_time`, which wraps the hour into `0..24`, the date here moves to the previous or the next day
+    /// whenever the UT of the event falls outside the UT day of `doy`, as it does for observers far from Greenwich
+    pub fn sunrise_datetime(&self, year: u16) -> Result<(u16, u8, u8, u8, u8, u8), SunMood> {
+        let ut = self.sunrise_local_mean_time(0.0)?.rem_euclid(24.0) - self.long / 15.0;
+        Ok(ut_datetime(year, self.doy, ut))
+    }
+
+    pub fn sunrise_time(&self) -> Result<f32, SunMood> {
+        self.sunrise_time_by_horizon(0.0)
+    }
 
-        let t = lha + ra - (0.06571 * t) - 6.622;
+    /// Sun Rise time for an observer whose horizon is raised (or lowered, if negative) by `horizon_altitude_deg` degrees.
+    /// The Sun is taken to rise when its center is 0.833° below that horizon, which allows for refraction and the Sun's radius, or as set by `limb`
+    pub fn sunrise_time_by_horizon(&self, horizon_altitude_deg: f32) -> Result<f32, SunMood> {
+        let local_offset = self.timezone;
+        let long_hour = self.long / 15.0;
+
+        let t = self.sunrise_local_mean_time(horizon_altitude_deg)?;
         let mut ut = t - long_hour;
 
         ut += local_offset;
@@ -236,17 +359,41 @@ impl SunRiseAndSet {
         Ok(ut)
     }
 
-    pub fn sunset_time(&self) -> Result<f32, SunMood> {
-        let lha = self.sunset_local_ha_in_deg()?;
+    /// Local mean time of the Sun Set in hours, not wrapped into `0..24`
+    fn sunset_local_mean_time(&self, horizon_altitude_deg: f32) -> Result<f32, SunMood> {
+        if let Some(t) = self.accurate_local_mean_time(false, horizon_altitude_deg) {
+            return t;
+        }
+
+        let lha = self.sunset_local_ha_by_horizon(horizon_altitude_deg)?;
+
         let ra = self.sunset_ra_in_hours();
-        let doy = self.doy;
-        let long = self.long;
-        let local_offset = self.timezone;
-        let long_hour = long / 15.0;
+        let long_hour = self.long / 15.0;
+
+        let t = self.doy as f32 + ((18.0 - long_hour) / 24.0);
 
-        let t = doy as f32 + ((18.0 - long_hour) / 24.0);
+        Ok(lha + ra - (0.06571 * t) - 6.622)
+    }
 
-        let t = lha + ra - (0.06571 * t) - 6.622;
+    /// Sun Set as a `(year, month, day, hour, min, sec)` date and time in UT for the given year of `doy`.
+    /// Unlike `sunset_time`, which wraps the hour into `0..24`, the date here moves to the previous or the next day
+    /// whenever the UT of the event falls outside the UT day of `doy`, as it does for observers far from Greenwich
+    pub fn sunset_datetime(&self, year: u16) -> Result<(u16, u8, u8, u8, u8, u8), SunMood> {
+        let ut = self.sunset_local_mean_time(0.0)?.rem_euclid(24.0) - self.long / 15.0;
+        Ok(ut_datetime(year, self.doy, ut))
+    }
+
+    pub fn sunset_time(&self) -> Result<f32, SunMood> {
+        self.sunset_time_by_horizon(0.0)
+    }
+
+    /// Sun Set time for an observer whose horizon is raised (or lowered, if negative) by `horizon_altitude_deg` degrees.
+    /// The Sun is taken to set when its center is 0.833° below that horizon, which allows for refraction and the Sun's radius, or as set by `limb`
+    pub fn sunset_time_by_horizon(&self, horizon_altitude_deg: f32) -> Result<f32, SunMood> {
+        let local_offset = self.timezone;
+        let long_hour = self.long / 15.0;
+
+        let t = self.sunset_local_mean_time(horizon_altitude_deg)?;
         let mut ut = t - long_hour;
 
         ut += local_offset;
@@ -260,74 +407,83 @@ impl SunRiseAndSet {
         Ok(ut)
     }
 
-    pub fn day_length(&self) -> Result<f32, SunMood> {
-        Ok(self.sunset_time()? - self.sunrise_time()?)
+    /// Sun Rise as a fractional day of the year on the local clock, for example `137.24` for 5:46 on May 16th 2024.
+    /// Unlike `sunrise_time`, an event falling before midnight of `doy` on the clock keeps a day below `doy`
+    pub fn sunrise_frac_doy(&self) -> Result<f64, SunMood> {
+        Ok(self.frac_doy(self.sunrise_local_mean_time(0.0)?))
     }
 
-    /// Sun Rise Right Ascension on the given day and location
-    pub fn sunrise_ra_in_hours(&self) -> f32 {
-        let stl = self.sunrise_true_long_in_deg();
-        let mut ra = (180.0 / PI) * (0.91764 * stl.to_radians().tan()).atan();
-
-        let mut ra = if ra < 0.0 {
-            ra += 360.0;
-            ra
-        } else if ra > 360.0 {
-            ra -= 360.0;
-            ra
-        } else {
-            ra
-        };
+    /// Sun Set as a fractional day of the year on the local clock, for example `138.002` for 0:03 on the day after `doy`.
+    /// Unlike `sunset_time`, which wraps the hour into `0..24`, an event after midnight moves to the next day
+    pub fn sunset_frac_doy(&self) -> Result<f64, SunMood> {
+        Ok(self.frac_doy(self.sunset_local_mean_time(0.0)?))
+    }
 
-        let l_quadrant = (stl / 90.0).floor() * 90.0;
-        let r_quadrant = (ra / 90.0).floor() * 90.0;
+    /// Converts the local mean time of an event on `doy` to a fractional day of the year on the local clock
+    fn frac_doy(&self, local_mean_time: f32) -> f64 {
+        let clock = local_mean_time.rem_euclid(24.0) - self.long / 15.0 + self.timezone;
+        // Day 1 starts at 1.0, so noon of doy is doy + 0.5
+        self.doy as f64 + clock as f64 / 24.0
+    }
 
-        ra = (ra + l_quadrant - r_quadrant) / 15.0;
+    /// Sun Rise time computed by the same algorithm as `sunrise_time`, but carried out in f64 from the f32 inputs.
+    /// With the accurate model set, this is `sunrise_time` widened to f64
+    pub fn sunrise_time_f64(&self) -> Result<f64, SunMood> {
+        if self.is_accurate() {
+            return self.sunrise_time().map(f64::from);
+        }
+        self.event_time_f64(true)
+    }
 
-        ra
+    /// Sun Set time computed by the same algorithm as `sunset_time`, but carried out in f64 from the f32 inputs.
+    /// With the accurate model set, this is `sunset_time` widened to f64
+    pub fn sunset_time_f64(&self) -> Result<f64, SunMood> {
+        if self.is_accurate() {
+            return self.sunset_time().map(f64::from);
+        }
+        self.event_time_f64(false)
     }
 
-    /// Sun Set Right Ascension on the given day and location
-    pub fn sunset_ra_in_hours(&self) -> f32 {
-        let stl = self.sunset_true_long_in_deg();
-        let mut ra = (180.0 / PI) * (0.91764 * stl.to_radians().tan()).atan();
-
-        let mut ra = if ra < 0.0 {
-            ra += 360.0;
-            ra
-        } else if ra > 360.0 {
-            ra -= 360.0;
-            ra
-        } else {
-            ra
-        };
+    /// Local time in hours within `0..24` of the Sun Rise (or Set) by the default model, in f64
+    fn event_time_f64(&self, rising: bool) -> Result<f64, SunMood> {
+        let long_hour = self.long as f64 / 15.0;
+        let hour = if rising { 6.0 } else { 18.0 };
+
+        let stl = f64::true_long_in_deg(self.mean_anomaly_at(hour));
+        let ra = f64::ra_in_hours(stl);
+        let ha = self.horizon_ha_in_deg(f64::declination(stl), 0.0)?;
+        let lha = if rising { 360.0 - ha } else { ha } / 15.0;
 
-        let l_quadrant = (stl / 90.0).floor() * 90.0;
-        let r_quadrant = (ra / 90.0).floor() * 90.0;
+        let t = self.doy as f64 + ((hour - long_hour) / 24.0);
+        let local_mean_time = lha + ra - (0.06571 * t) - 6.622;
+
+        Ok((local_mean_time - long_hour + self.timezone as f64).rem_euclid(24.0))
+    }
+
+    pub fn day_length(&self) -> Result<f32, SunMood> {
+        Ok(self.sunset_time()? - self.sunrise_time()?)
+    }
 
-        ra = (ra + l_quadrant - r_quadrant) / 15.0;
+    /// Sun Rise Right Ascension on the given day and location
+    pub fn sunrise_ra_in_hours(&self) -> f32 {
+        SunRiseFloat::ra_in_hours(self.sunrise_true_long_in_deg())
+    }
 
-        ra
+    /// Sun Set Right Ascension on the given day and location
+    pub fn sunset_ra_in_hours(&self) -> f32 {
+        SunRiseFloat::ra_in_hours(self.sunset_true_long_in_deg())
     }
 
     /// Sun Rise Local Hour Angle on the given day and location.
     /// This returns a Result<> as there are locations where the Sun never rises on a given day
     pub fn sunrise_local_ha_in_deg(&self) -> Result<f32, SunMood> {
-        let dec = self.sunrise_declination();
-        let lat = self.lat;
-        let cos_lha = (ZENITH.to_radians().cos()
-            - (dec.to_radians().sin() * lat.to_radians().sin()))
-            / (dec.to_radians().cos() * lat.to_radians().cos());
-
-        if cos_lha > 1.0 {
-            return Err(SunMood::NeverRise);
-        } else if cos_lha < -1.0 {
-            return Err(SunMood::NeverSet);
-        } else {
-            //
-        }
+        self.sunrise_local_ha_by_horizon(0.0)
+    }
 
-        let ha = (180.0 / PI) * cos_lha.acos();
+    /// Sun Rise Local Hour Angle on the given day and location for a horizon raised by `horizon_altitude_deg` degrees.
+    /// This returns a Result<> as there are locations where the Sun never clears such a horizon on a given day
+    pub fn sunrise_local_ha_by_horizon(&self, horizon_altitude_deg: f32) -> Result<f32, SunMood> {
+        let ha = self.horizon_ha_in_deg(self.sunrise_declination(), horizon_altitude_deg)?;
         let ha = 360.0 - ha;
         Ok(ha / 15.0)
     }
@@ -335,24 +491,272 @@ impl SunRiseAndSet {
     /// Sun Set Local Hour Angle on the given day and location.
     /// This returns a Result<> as there are locations where the Sun never sets on a given day
     pub fn sunset_local_ha_in_deg(&self) -> Result<f32, SunMood> {
-        let dec = self.sunset_declination();
-        let lat = self.lat;
-        let cos_lha = (ZENITH.to_radians().cos()
-            - (dec.to_radians().sin() * lat.to_radians().sin()))
-            / (dec.to_radians().cos() * lat.to_radians().cos());
-
-        if cos_lha > 1.0 {
-            return Err(SunMood::NeverRise);
-        } else if cos_lha < -1.0 {
-            return Err(SunMood::NeverSet);
-        } else {
-            //
-        }
+        self.sunset_local_ha_by_horizon(0.0)
+    }
 
-        let ha = (180.0 / PI) * cos_lha.acos();
-        let ha = ha;
+    /// Sun Set Local Hour Angle on the given day and location for a horizon raised by `horizon_altitude_deg` degrees.
+    /// This returns a Result<> as there are locations where the Sun never drops below such a horizon on a given day
+    pub fn sunset_local_ha_by_horizon(&self, horizon_altitude_deg: f32) -> Result<f32, SunMood> {
+        let ha = self.horizon_ha_in_deg(self.sunset_declination(), horizon_altitude_deg)?;
         Ok(ha / 15.0)
     }
 
+    /// Sun Rise and Sun Set Local Hour Angles on the given day and location, in the same units as
+    /// `sunrise_local_ha_in_deg` and `sunset_local_ha_in_deg`, from a single declination taken at local noon.
+    /// They differ from those by the small change of the declination between noon and the morning or evening.
+    /// This returns a Result<> as there are locations where the Sun never rises or never sets on a given day
+    pub fn local_ha_pair(&self) -> Result<(f32, f32), SunMood> {
+        let ha = self.horizon_ha_in_deg(self.declination_at(12), 0.0)?;
+        Ok(((360.0 - ha) / 15.0, ha / 15.0))
+    }
+
+    /// Hour angle in degrees, within `[0, 180]`, at which the Sun of declination `dec` crosses a horizon
+    /// raised by `horizon_altitude_deg` degrees
+    fn horizon_ha_in_deg<T: SunRiseFloat>(&self, dec: T, horizon_altitude_deg: T) -> Result<T, SunMood> {
+        T::horizon_ha_in_deg(dec, self.lat, self.limb.zenith_in_deg(), horizon_altitude_deg)
+    }
+
+    /// Sun Rise Azimuth in degrees (measured from the north towards the east) on the given day and location.
+    /// This returns a Result<> as there are locations where the Sun never rises on a given day
+    pub fn sunrise_azimuth(&self) -> Result<f32, SunMood> {
+        self.sunrise_local_ha_in_deg()?;
+        Ok(horizon_azimuth(self.sunrise_declination(), self.lat, self.limb.zenith_f32()))
+    }
+
+    /// Sun Set Azimuth in degrees (measured from the north towards the east) on the given day and location.
+    /// This returns a Result<> as there are locations where the Sun never sets on a given day
+    pub fn sunset_azimuth(&self) -> Result<f32, SunMood> {
+        self.sunset_local_ha_in_deg()?;
+        Ok(360.0 - horizon_azimuth(self.sunset_declination(), self.lat, self.limb.zenith_f32()))
+    }
+
+    /// Sun Local Hour Angle in hours for any local time on the given day and location.
+    /// The value is signed, negative before solar noon and positive after it, and lies within `(-12, 12]`
+    ///
+    /// # Arguments
+    /// * `hour`: Local time of interest in | `Decimal Hours` (24 hour format)
+    pub fn current_hour_angle(&self, hour: f32) -> f32 {
+        let long_hour = self.long / 15.0;
+        let local_mean_time = hour - self.timezone + long_hour;
+
+        let t = self.doy as f32 + ((local_mean_time - long_hour) / 24.0);
+        let ra = f32::ra_in_hours(f32::true_long_in_deg(self.mean_anomaly_at(local_mean_time)));
+        let ha = local_mean_time - ra + (0.06571 * t) + 6.622;
+
+        12.0 - (12.0 - ha).rem_euclid(24.0)
+    }
+
+}
+
+/// Equation of center of the Sun in degrees: the difference between its true and mean anomaly,
+/// by the two term series `1.916 sin M + 0.020 sin 2M` of the sun rise algorithm
+///
+/// # Arguments
+/// * `mean_anomaly_deg`: Mean anomaly of the Sun in | `Decimal Degrees`
+///
+/// # Example
+/// ```
+/// use astronav::coords::sun::equation_of_center;
+///
+/// // Largest near the quadratures of the orbit, and zero at perihelion and aphelion
+/// assert!((equation_of_center(90.0) - 1.916).abs() < 1e-9);
+/// assert!(equation_of_center(180.0).abs() < 1e-9);
+/// ```
+pub fn equation_of_center(mean_anomaly_deg: f64) -> f64 {
+    (1.916 * mean_anomaly_deg.to_radians().sin()) + (0.020 * (2.0 * mean_anomaly_deg).to_radians().sin())
 }
 
+/// Returns the Sun's apparent ecliptic longitude in degrees within `[0, 360)`: its mean longitude corrected by
+/// the equation of center, the nutation in longitude and the aberration (Meeus, Chapter 25). It is 0° at the March equinox
+/// and grows by about 1° a day
+///
+/// # Arguments
+/// * `jt`: Julian Time on the Terrestrial Time (TT) scale
+///
+/// # Example
+/// ```
+/// use astronav::coords::sun::sun_ecliptic_longitude;
+///
+/// // October 13th 1992, 0h TD (Meeus, Example 25.a)
+/// assert!((sun_ecliptic_longitude(2448908.5) - 199.90895).abs() < 1e-3);
+/// ```
+pub fn sun_ecliptic_longitude(jt: f64) -> f64 {
+    let t = julian_centuries(jt);
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t.powi(2);
+    let m = (357.52911 + 35999.05029 * t - 0.0001537 * t.powi(2)).to_radians();
+    let c = (1.914602 - 0.004817 * t - 0.000014 * t.powi(2)) * m.sin()
+        + (0.019993 - 0.000101 * t) * (2.0 * m).sin()
+        + 0.000289 * (3.0 * m).sin();
+    let omega = (125.04 - 1934.136 * t).to_radians();
+
+    (l0 + c - 0.00569 - 0.00478 * omega.sin()).rem_euclid(360.0)
+}
+
+/// Apparent declination of the Sun in degrees from its apparent ecliptic longitude and the true obliquity (Meeus, Chapter 25)
+#[cfg(feature = "noaa-sun")]
+fn apparent_declination(jt: f64) -> f64 {
+    let obliquity = (mean_obliquity_in_degrees(jt) + nutation_in_degrees(jt).1).to_radians();
+    (obliquity.sin() * sun_ecliptic_longitude(jt).to_radians().sin()).asin().to_degrees()
+}
+
+/// Returns the Sun's equatorial horizontal parallax in degrees, 8.794″ at 1 AU: the angle the Earth's equatorial radius
+/// subtends seen from the Sun. It lowers the Sun seen from the ground by at most this much, on the horizon
+///
+/// # Arguments
+/// * `distance_au`: Distance between the Earth and the Sun in astronomical units, such as `NOAASun::distance_in_au`
+///
+/// # Example
+/// ```
+/// use astronav::coords::sun::sun_horizontal_parallax;
+///
+/// // In early January, at perihelion
+/// assert!((sun_horizontal_parallax(0.9833) * 3600.0 - 8.943).abs() < 1e-3);
+/// ```
+pub fn sun_horizontal_parallax(distance_au: f64) -> f64 {
+    8.794 / 3600.0 / distance_au
+}
+
+/// Declination of the Sun at the solstices in degrees, the obliquity of the ecliptic
+const SOLSTICE_DECLINATION: f64 = 23.44;
+
+/// Altitude of the Sun in degrees at solar noon on the June solstice, when its declination is +23.44°,
+/// seen from the latitude `lat` in degrees. This is the highest noon Sun of the year north of the tropic of Cancer,
+/// and the lowest south of the tropic of Capricorn. It is negative where the Sun does not rise on the day
+///
+/// # Example
+/// ```
+/// use astronav::coords::sun::summer_solstice_noon_altitude;
+///
+/// // Overhead on the tropic of Cancer
+/// assert!((summer_solstice_noon_altitude(23.44) - 90.0).abs() < 1e-9);
+/// ```
+pub fn summer_solstice_noon_altitude(lat: f64) -> f64 {
+    noon_altitude(lat, SOLSTICE_DECLINATION)
+}
+
+/// Altitude of the Sun in degrees at solar noon on the December solstice, when its declination is -23.44°,
+/// seen from the latitude `lat` in degrees. See `summer_solstice_noon_altitude`
+///
+/// # Example
+/// ```
+/// use astronav::coords::sun::winter_solstice_noon_altitude;
+///
+/// // The Sun does not come up inside the arctic circle
+/// assert!(winter_solstice_noon_altitude(70.0) < 0.0);
+/// ```
+pub fn winter_solstice_noon_altitude(lat: f64) -> f64 {
+    noon_altitude(lat, -SOLSTICE_DECLINATION)
+}
+
+/// Altitude in degrees of a body of declination `dec` on the meridian, above the pole or the equator
+fn noon_altitude(lat: f64, dec: f64) -> f64 {
+    90.0 - (lat - dec).abs()
+}
+
+/// The steps of the sun rise algorithm, implemented for f32 and f64 so that the f64 methods of `SunRiseAndSet`
+/// carry out the very same algorithm as the f32 ones
+trait SunRiseFloat: Copy {
+    /// Mean anomaly of the Sun in degrees at the given local hour of the day `doy`, at the longitude `long`
+    fn mean_anomaly(doy: u16, long: f32, hour: Self) -> Self;
+    /// True longitude of the Sun in degrees, within `[0, 360]`, for a given mean anomaly
+    fn true_long_in_deg(sma: Self) -> Self;
+    /// Right Ascension of the Sun in hours for a given true longitude, placed in the same quadrant as the longitude
+    fn ra_in_hours(stl: Self) -> Self;
+    /// Declination of the Sun in degrees for a given true longitude
+    fn declination(stl: Self) -> Self;
+    /// Hour angle in degrees, within `[0, 180]`, at which the Sun of declination `dec` crosses a horizon raised by
+    /// `horizon_altitude_deg` degrees, taking the rise and set at the zenith angle `zenith` of the Sun's center
+    fn horizon_ha_in_deg(dec: Self, lat: f32, zenith: f64, horizon_altitude_deg: Self) -> Result<Self, SunMood>;
+}
+
+macro_rules! impl_sun_rise_float {
+    ($t:ident) => {
+        impl SunRiseFloat for $t {
+            fn mean_anomaly(doy: u16, long: f32, hour: Self) -> Self {
+                let long_hour = long as $t / 15.0;
+
+                let t = doy as $t + ((hour - long_hour) / 24.0);
+
+                (0.9856 * t) - 3.289
+            }
+
+            fn true_long_in_deg(sma: Self) -> Self {
+                let mut l = sma + equation_of_center(sma as f64) as $t + 282.634;
+
+                if l < 0.0 {
+                    l += 360.0;
+                    l
+                } else if l > 360.0 {
+                    l -= 360.0;
+                    l
+                } else {
+                    l
+                }
+            }
+
+            fn ra_in_hours(stl: Self) -> Self {
+                let mut ra = (180.0 / std::$t::consts::PI) * (0.91764 * stl.to_radians().tan()).atan();
+
+                let mut ra = if ra < 0.0 {
+                    ra += 360.0;
+                    ra
+                } else if ra > 360.0 {
+                    ra -= 360.0;
+                    ra
+                } else {
+                    ra
+                };
+
+                let l_quadrant = (stl / 90.0).floor() * 90.0;
+                let r_quadrant = (ra / 90.0).floor() * 90.0;
+
+                ra = (ra + l_quadrant - r_quadrant) / 15.0;
+
+                ra
+            }
+
+            fn declination(stl: Self) -> Self {
+                (0.39782 * stl.to_radians().sin()).asin().to_degrees()
+            }
+
+            fn horizon_ha_in_deg(dec: Self, lat: f32, zenith: f64, horizon_altitude_deg: Self) -> Result<Self, SunMood> {
+                let zenith = zenith as $t - horizon_altitude_deg;
+                let lat = lat as $t;
+                let cos_lha = (zenith.to_radians().cos()
+                    - (dec.to_radians().sin() * lat.to_radians().sin()))
+                    / (dec.to_radians().cos() * lat.to_radians().cos());
+
+                if cos_lha > 1.0 {
+                    return Err(SunMood::NeverRise);
+                } else if cos_lha < -1.0 {
+                    return Err(SunMood::NeverSet);
+                } else {
+                    //
+                }
+
+                let ha = (180.0 / std::$t::consts::PI) * cos_lha.acos();
+                Ok(ha)
+            }
+        }
+    };
+}
+
+impl_sun_rise_float!(f32);
+impl_sun_rise_float!(f64);
+
+/// Azimuth in degrees of the Sun as it reaches the zenith angle `zenith` in the morning
+fn horizon_azimuth(dec: f32, lat: f32, zenith: f32) -> f32 {
+    let alt = 90.0 - zenith;
+    let cos_az = (dec.to_radians().sin() - (alt.to_radians().sin() * lat.to_radians().sin()))
+        / (alt.to_radians().cos() * lat.to_radians().cos());
+
+    cos_az.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// Date and time in UT of an instant given in hours from the start of the UT day `doy` of `year`
+fn ut_datetime(year: u16, doy: u16, ut_hours: f32) -> (u16, u8, u8, u8, u8, u8) {
+    let (month, day) = day_of_year_to_date(year, doy);
+    let t = AstroTime::new(day, month, year, 0, 0, 0, 0.0).plus_seconds((ut_hours as f64 * 3600.0).round() as i64);
+
+    (t.year, t.month, t.day, t.hour, t.min, t.sec)
+}
@@ -1,5 +1,6 @@
 // Copyright (c) 2024 Venkatesh Omkaram
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
+pub mod approx;
 pub mod coords;
 pub mod time;
\ No newline at end of file
@@ -3,48 +3,161 @@
 
 /**
 Computes the Julian day number by a given day, month and year
+
+The arithmetic is carried out in wide signed integers, so January and February,
+which are counted as months 13 and 14 of the previous year, cannot underflow
 **/
 pub fn julian_day_number(day: u8, month: u8, year: u16) -> u32 {
-    let a = (((14 - month) / 12) as f32).floor() as u8;
-    let y = year + 4800 - a as u16;
+    let (day, month, year) = (day as i64, month as i64, year as i64);
+
+    let a = (14 - month) / 12;
+    let y = year + 4800 - a;
     let m = month + (12 * a) - 3;
 
-    let jd = day as u32
-        + (((153 * m as u16 + 2) / 5) as f32).floor() as u32
-        + (365 * y as u32) as u32
-        + ((y / 4) as f32).floor() as u32
-        - ((y / 100) as f32).floor() as u32
-        + ((y / 400) as f32).floor() as u32
-        - 32045;
+    (day + ((153 * m + 2) / 5) + (365 * y) + (y / 4) - (y / 100) + (y / 400) - 32045) as u32
+}
+
+/// Converts a Julian day number back to the Gregorian calendar `(day, month, year)`
+fn date_from_julian_day_number(jdn: i64) -> (u8, u8, u16) {
+    let a = jdn + 32044;
+    let b = (4 * a + 3) / 146097;
+    let c = a - (146097 * b) / 4;
+    let d = (4 * c + 3) / 1461;
+    let e = c - (1461 * d) / 4;
+    let m = (5 * e + 2) / 153;
+
+    let day = e - ((153 * m + 2) / 5) + 1;
+    let month = m + 3 - 12 * (m / 10);
+    let year = 100 * b + d - 4800 + m / 10;
 
-    jd
+    (day as u8, month as u8, year as u16)
 }
 
+/// The difference `TT - UT` (Delta T) in seconds assumed by this crate
+pub const DELTA_T_SECS: f64 = 74.0;
+
 /**
  * Computes the Julian Time by a given Julian day number, hour, minutes, seconds
+ * 
+ * The returned value is on the Terrestrial Time (TT) scale, the same as `julian_time_tt`
  **/
 pub fn julian_time(julian_day: u32, hour: u8, min: u8, sec: u8, timezone: f32) -> f64 {
-    let delta_t = 74.0/86400.0;
-    let jt =
-        julian_day as f64 + ((hour as f64 - 12.0) / 24.0) + (min as f64 / 1440.0) + (sec as f64 / 86400.0)
-        - timezone as f64 / 24.0 + delta_t;
-    jt
+    julian_time_tt(julian_day, hour, min, sec, timezone)
+}
+
+/**
+ * Computes the Julian Time on the Universal Time (UT) scale by a given Julian day number, hour, minutes, seconds
+ * 
+ * Use UT for anything tied to the rotation of the Earth, such as `gmst_in_degrees`. Note that the sidereal times
+ * of `AstroTime` are computed from its TT `julian_time` instead
+ **/
+pub fn julian_time_ut(julian_day: u32, hour: u8, min: u8, sec: u8, timezone: f32) -> f64 {
+    julian_day as f64 + ((hour as f64 - 12.0) / 24.0) + (min as f64 / 1440.0) + (sec as f64 / 86400.0)
+        - timezone as f64 / 24.0
+}
+
+/**
+ * Computes the Julian Time on the Terrestrial Time (TT) scale by a given Julian day number, hour, minutes, seconds
+ * 
+ * TT is UT shifted by `DELTA_T_SECS`. Use TT for the positions of the Sun, the Moon and the planets
+ **/
+pub fn julian_time_tt(julian_day: u32, hour: u8, min: u8, sec: u8, timezone: f32) -> f64 {
+    ut_to_tt(julian_time_ut(julian_day, hour, min, sec, timezone))
+}
+
+/// Converts a Julian Time on the UT scale to the TT scale
+pub fn ut_to_tt(julian_time_ut: f64) -> f64 {
+    julian_time_ut + DELTA_T_SECS / 86400.0
+}
+
+/// Converts a Julian Time on the TT scale to the UT scale
+pub fn tt_to_ut(julian_time_tt: f64) -> f64 {
+    julian_time_tt - DELTA_T_SECS / 86400.0
+}
+
+/**
+ * Computes the Julian centuries elapsed since J2000.0 (JD 2451545.0) by a given Julian Time
+ * 
+ * # Returns
+ *  Julian centuries of 36525 days, negative before J2000.0
+ **/
+pub fn julian_centuries(julian_time: f64) -> f64 {
+    (julian_time - 2451545.0) / 36525.0
 }
 
 /**
  * Computes the Greenwich Mean Sidereal Time by a given Julian Time
  * 
+ * The formula is defined on the Universal Time (UT) scale, so pass `julian_time_ut` for the sidereal time of the rotating Earth.
+ * Passing a TT Julian Time, as `AstroTime::gmst_in_degrees` does, gives a sidereal time `DELTA_T_SECS` later, about 0.31° ahead
+ * 
  * # Returns
  *  Greenwich Mean Sidereal Time in `Decimal Degrees` 
  **/
 pub fn gmst_in_degrees(julian_time: f64) -> f64 {
     let jdt_tt = julian_time - 2451545.0;
-    let frac_time_elapsed = jdt_tt / 36525.0;
-    let gmst =
-        (280.46061837 + (360.98564736629 * jdt_tt) + (0.000387933 * frac_time_elapsed.powi(2))
-            - (frac_time_elapsed.powi(3) / 38710000.0))
-            .rem_euclid(360.0);
-    gmst
+    let frac_time_elapsed = julian_centuries(julian_time);
+    (280.46061837 + (360.98564736629 * jdt_tt) + (0.000387933 * frac_time_elapsed.powi(2))
+        - (frac_time_elapsed.powi(3) / 38710000.0))
+        .rem_euclid(360.0)
+}
+
+/**
+ * Computes the Nutation in longitude and in obliquity by a given Julian Time,
+ * using the four largest terms (Meeus, Astronomical Algorithms, Chapter 22), which is good to about 0.5"
+ * 
+ * # Returns
+ *  `(nutation in longitude, nutation in obliquity)` in `Decimal Degrees`
+ **/
+pub fn nutation_in_degrees(julian_time: f64) -> (f64, f64) {
+    let t = julian_centuries(julian_time);
+    // Longitude of the ascending node of the Moon's orbit and the mean longitudes of the Sun and the Moon
+    let omega = (125.04452 - 1934.136261 * t).to_radians();
+    let l_sun = (280.4665 + 36000.7698 * t).to_radians();
+    let l_moon = (218.3165 + 481267.8813 * t).to_radians();
+
+    let d_psi = -17.20 * omega.sin() - 1.32 * (2.0 * l_sun).sin() - 0.23 * (2.0 * l_moon).sin()
+        + 0.21 * (2.0 * omega).sin();
+    let d_eps = 9.20 * omega.cos() + 0.57 * (2.0 * l_sun).cos() + 0.10 * (2.0 * l_moon).cos()
+        - 0.09 * (2.0 * omega).cos();
+
+    (d_psi / 3600.0, d_eps / 3600.0)
+}
+
+/**
+ * Computes the mean obliquity of the ecliptic by a given Julian Time
+ * 
+ * # Returns
+ *  Mean obliquity of the ecliptic in `Decimal Degrees`
+ **/
+pub fn mean_obliquity_in_degrees(julian_time: f64) -> f64 {
+    let t = julian_centuries(julian_time);
+    23.0 + 26.0 / 60.0 + (21.448 - 46.8150 * t - 0.00059 * t.powi(2) + 0.001813 * t.powi(3)) / 3600.0
+}
+
+/**
+ * Computes the Equation of the Equinoxes, the difference between the apparent and the mean sidereal time, by a given Julian Time
+ * 
+ * # Returns
+ *  Equation of the Equinoxes in `Decimal Degrees` (never more than about 1.2 seconds of time)
+ **/
+pub fn equation_of_equinoxes(julian_time: f64) -> f64 {
+    let (d_psi, d_eps) = nutation_in_degrees(julian_time);
+    let true_obliquity = mean_obliquity_in_degrees(julian_time) + d_eps;
+
+    d_psi * true_obliquity.to_radians().cos()
+}
+
+/**
+ * Computes the Greenwich Apparent Sidereal Time by a given Julian Time
+ * 
+ * The Julian Time is taken on the same scale as `gmst_in_degrees`, which expects UT. The small nutation terms do not mind the scale
+ * 
+ * # Returns
+ *  Greenwich Apparent Sidereal Time in `Decimal Degrees` 
+ **/
+pub fn gast_in_degrees(julian_time: f64) -> f64 {
+    (gmst_in_degrees(julian_time) + equation_of_equinoxes(julian_time)).rem_euclid(360.0)
 }
 
 /**
@@ -61,15 +174,100 @@ pub fn lmst_in_degrees(gmst_in_deg: f64, longitude: f64) -> f64 {
     (gmst_in_deg + longitude).rem_euclid(360.0)
 }
 
+/**
+ * Computes the length of the mean sidereal day by a given Julian Time, from the rate of the polynomial in `gmst_in_degrees`
+ * 
+ * # Returns
+ *  Length of the sidereal day in mean solar `Seconds`, about 86164.09 at J2000
+ **/
+pub fn sidereal_day_seconds(julian_time: f64) -> f64 {
+    let frac_time_elapsed = julian_centuries(julian_time);
+    // Degrees of GMST gained per day
+    let rate = 360.98564736629 + (2.0 * 0.000387933 * frac_time_elapsed / 36525.0)
+        - (3.0 * frac_time_elapsed.powi(2) / (38710000.0 * 36525.0));
+
+    86400.0 * 360.0 / rate
+}
+
+/// Ratio of the sidereal rate to the solar rate, i.e. sidereal seconds elapsed per solar second
+pub const SIDEREAL_RATE: f64 = 1.00273790935;
+
+/**
+ * Advances a Local Sidereal Time by the elapsed solar (clock) time without computing the Julian Time again
+ * 
+ * # Arguments
+ * * `lst_deg`: Local Sidereal Time in | `Decimal Degrees floating point`
+ * * `elapsed_solar_seconds`: Clock time elapsed in | `Seconds` (negative values step backwards)
+ * 
+ * # Returns
+ *  Local Sidereal Time in `Decimal Degrees` within `[0, 360)`
+ **/
+pub fn lst_advance(lst_deg: f64, elapsed_solar_seconds: f64) -> f64 {
+    (lst_deg + elapsed_solar_seconds * SIDEREAL_RATE * (360.0 / 86400.0)).rem_euclid(360.0)
+}
+
+/**
+ * Computes the Julian Epoch (Example: J2000.0) by a given Julian Time
+ * 
+ * # Returns
+ *  Julian Epoch in `Decimal Years`
+ **/
+pub fn julian_epoch(julian_time: f64) -> f64 {
+    2000.0 + (julian_time - 2451545.0) / 365.25
+}
+
+/**
+ * Computes the Besselian Epoch (Example: B1950.0) by a given Julian Time, also known as the Besselian year,
+ * as used by FK4 catalogues and older almanac formulas
+ * 
+ * # Returns
+ *  Besselian Epoch in `Decimal Years`
+ * 
+ * # Example
+ * ```
+ * use astronav::time::besselian_epoch;
+ * 
+ * assert!((besselian_epoch(2433282.4235) - 1950.0).abs() < 1e-6);
+ * ```
+ **/
+pub fn besselian_epoch(julian_time: f64) -> f64 {
+    1900.0 + (julian_time - 2415020.31352) / 365.242198781
+}
+
+/**
+ * Computes the Besselian year by a given Julian Time, the same as `besselian_epoch`
+ * 
+ * # Returns
+ *  Besselian year in `Decimal Years`
+ **/
+pub fn besselian_year(julian_time: f64) -> f64 {
+    besselian_epoch(julian_time)
+}
+
+/**
+ * Computes the Julian Time by a given Julian Epoch in `Decimal Years`
+ **/
+pub fn jt_from_julian_epoch(epoch: f64) -> f64 {
+    2451545.0 + (epoch - 2000.0) * 365.25
+}
+
+/**
+ * Computes the Julian Time by a given Besselian Epoch in `Decimal Years`
+ **/
+pub fn jt_from_besselian_epoch(epoch: f64) -> f64 {
+    2415020.31352 + (epoch - 1900.0) * 365.242198781
+}
+
 /// Computes the day of the year
 pub fn day_of_year(year: u16, month: u8, day: u8) -> u16 {
         let n1 = (275 * month as u16) / 9;
         let n2 = ((month + 9) / 12) as u16 * (1 + ((year - 4 * (year / 4) + 2) / 3));
         let n3 = 30_u16;
-        (n1 - n2 + day as u16 - n3).into()
+        n1 - n2 + day as u16 - n3
 }
 
-/// Computes the month and day from the day of the year
+/// Computes the month and day from the day of the year, which must be within `1..=365` (`1..=366` in leap years).
+/// A day on a month boundary is the last day of that month, so day 31 is January 31st and day 60 of a leap year is February 29th
 /// 
 /// # Returns `(month, day)` as a tuple
 pub fn day_of_year_to_date(year: u16, day_of_year: u16) -> (u8, u8) {
@@ -92,29 +290,21 @@ pub fn day_of_year_to_date(year: u16, day_of_year: u16) -> (u8, u8) {
     (month as u8, day as u8)
 }
 
-/// Computes the fractional day of the year by the hour
+/// Computes the fractional day of the year by the hour: the days elapsed since 0h UT on January 1st, so 0.0 at the start of the year
 pub fn frac_day_of_year(year: u16, month: u8, day: u8, timezone: f32, hour: f32) -> f32 {
-    let days_in_year = if is_leap_year(year) {
-        366.0
-    } else {
-        365.0
-    };
     let doy = day_of_year(year, month, day);
-    let fy = (doy as f32 / days_in_year) + (doy as f32 - 1.0) - (timezone/24.0) + (hour/24.0);
-    fy
+    (doy as f32 - 1.0) - (timezone/24.0) + (hour/24.0)
 }
 
 pub fn is_leap_year(year: u16) -> bool {
-    if (year % 4 == 0 && !(year % 100 == 0)) || (year % 400 == 0) {
-        true
-    } else {
-        false
-    }
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
 }
 
 /**
  * Use this struct if do not wish to use free standing functions in the `time` module.
+ * Since 0.3.0 it also holds `millis`, so a struct literal written for 0.2 needs `millis: 0` added
  **/
+#[derive(Debug, Clone, Default)]
 pub struct AstroTime {
     pub day: u8,
     pub month: u8,
@@ -122,24 +312,56 @@ pub struct AstroTime {
     pub hour: u8,
     pub min: u8,
     pub sec: u8,
+    /// Milliseconds past `sec`, also set by `with_millis`
+    pub millis: u16,
     pub timezone: f32
 }
 
 impl AstroTime {
 
+/**
+ * Returns an AstroTime for whole seconds, with `millis` set to zero
+**/
+    pub fn new(day: u8, month: u8, year: u16, hour: u8, min: u8, sec: u8, timezone: f32) -> Self {
+        Self { day, month, year, hour, min, sec, millis: 0, timezone }
+    }
+
+/**
+ * Returns a copy of this AstroTime with the milliseconds past the second set
+**/
+    pub fn with_millis(&self, millis: u16) -> Self {
+        Self { millis, ..self.clone() }
+    }
+
+/**
+ * Returns the milliseconds past the second
+**/
+    pub fn millis(&self) -> u16 {
+        self.millis
+    }
+
 /**
  * Returns the Greenwich Mean Sidereal Time in `Decimal Degrees`
+ * 
+ * This is computed from the Terrestrial Time (TT) `julian_time`, as it has been since the first release, and so runs
+ * `DELTA_T_SECS` (about 0.31°) ahead of the sidereal time of the Earth. Use `gmst_in_degrees(self.julian_time_ut())`
+ * for the sidereal time on the Universal Time (UT) scale
 **/
     pub fn gmst_in_degrees(&self) -> f64 {
         let julian_time = self.julian_time();
         let jdt_tt = julian_time - 2451545.0;
-        let frac_time_elapsed = jdt_tt / 36525.0;
+        let frac_time_elapsed = julian_centuries(julian_time);
+
+        (280.46061837 + (360.98564736629 * jdt_tt) + (0.000387933 * frac_time_elapsed.powi(2))
+            - (frac_time_elapsed.powi(3) / 38710000.0))
+            .rem_euclid(360.0)
+    }
 
-        let gmst =
-            (280.46061837 + (360.98564736629 * jdt_tt) + (0.000387933 * frac_time_elapsed.powi(2))
-                - (frac_time_elapsed.powi(3) / 38710000.0))
-                .rem_euclid(360.0);
-        gmst
+/**
+ * Returns the Julian centuries elapsed since J2000.0 on the Terrestrial Time (TT) scale
+**/
+    pub fn julian_centuries_j2000(&self) -> f64 {
+        julian_centuries(self.julian_time())
     }
 
 /**
@@ -150,15 +372,34 @@ impl AstroTime {
     }
 
 /**
- * Returns the Julian Time
+ * Returns the Julian Time on the Terrestrial Time (TT) scale
 **/
     pub fn julian_time(&self) -> f64 {
         julian_time(self.julian_day_number(), self.hour, self.min, self.sec, self.timezone)
+            + self.millis as f64 / 86_400_000.0
+    }
+
+/**
+ * Returns the Julian Time on the Universal Time (UT) scale
+**/
+    pub fn julian_time_ut(&self) -> f64 {
+        julian_time_ut(self.julian_day_number(), self.hour, self.min, self.sec, self.timezone)
+            + self.millis as f64 / 86_400_000.0
+    }
+
+/**
+ * Returns the Julian Time on the Terrestrial Time (TT) scale
+**/
+    pub fn julian_time_tt(&self) -> f64 {
+        julian_time_tt(self.julian_day_number(), self.hour, self.min, self.sec, self.timezone)
+            + self.millis as f64 / 86_400_000.0
     }
 
 /**
  * Compute and return the Local Mean Sidereal Time by a given Longitude in `Decimal Degrees` 
  * 
+ * This is based on `AstroTime::gmst_in_degrees`, and so on the Terrestrial Time (TT) scale like it
+ * 
  * # Arguments
  * * `longitude`: Longitude of the Local Meridian in | `Decimal Degrees floating point`
  **/
@@ -166,9 +407,23 @@ impl AstroTime {
         lmst_in_degrees(self.gmst_in_degrees(), longitude)
     }
 
+/**
+ * Compute and return the Local Apparent Sidereal Time by a given Longitude in `Decimal Degrees`.
+ * This is the Local Mean Sidereal Time corrected by the Equation of the Equinoxes, use it for precise hour angles.
+ * Like `lmst_in_degrees` it is computed from the Terrestrial Time (TT) `julian_time`
+ * 
+ * # Arguments
+ * * `longitude`: Longitude of the Local Meridian in | `Decimal Degrees floating point`
+ **/
+    pub fn last_in_degrees(&self, longitude: f64) -> f64 {
+        lmst_in_degrees(gast_in_degrees(self.julian_time()), longitude)
+    }
+
 /**
  * Computes the Local Mean Sidereal Time by a given Longitude in `Decimal Degrees`
  * 
+ * This is `lmst_in_degrees` in hours, on the Terrestrial Time (TT) scale like it
+ * 
  * # Arguments
  * * `longitude`: Longitude of the Local Meridian in | `Decimal Degrees floating point`
  **/
@@ -180,5 +435,83 @@ impl AstroTime {
     pub fn day_of_year(&self) -> u16 {
        day_of_year(self.year, self.month, self.day)
     }
+
+/**
+ * Returns a copy of this AstroTime with a new timezone offset.
+ * 
+ * The local clock fields are left untouched, so the returned value refers to a different instant
+ * and its Julian Time and sidereal times are derived again from the new offset.
+ * This crate does not know about daylight saving rules, so use this when your own DST logic decides the offset.
+ * 
+ * # Arguments
+ * * `offset_hours`: Timezone offset in | `Decimal Hours` (+ east, - west)
+ **/
+    pub fn with_offset(&self, offset_hours: f32) -> Self {
+        Self { timezone: offset_hours, ..self.clone() }
+    }
+
+/**
+ * Returns a copy of this AstroTime moved by a number of seconds, rolling the minutes, hours, days, months
+ * and years over as needed. The timezone and the milliseconds are kept.
+ * 
+ * # Arguments
+ * * `seconds`: Seconds to add in | `Seconds` (negative values step backwards)
+ **/
+    pub fn plus_seconds(&self, seconds: i64) -> Self {
+        let total = self.local_seconds() + seconds;
+        let (day, month, year) = date_from_julian_day_number(total.div_euclid(86400));
+        let secs_of_day = total.rem_euclid(86400);
+
+        Self {
+            day,
+            month,
+            year,
+            hour: (secs_of_day / 3600) as u8,
+            min: (secs_of_day % 3600 / 60) as u8,
+            sec: (secs_of_day % 60) as u8,
+            ..self.clone()
+        }
+    }
+
+/**
+ * Returns an iterator over the times from this AstroTime up to and including `end`, `step_minutes` apart.
+ * 
+ * A negative step walks backwards down to `end`, and a zero step yields nothing.
+ * The times keep the timezone of this AstroTime and are compared with `end` as instants, so `end` may use another timezone.
+ * 
+ * # Arguments
+ * * `end`: The last time of the span
+ * * `step_minutes`: Minutes between two successive times
+ **/
+    pub fn range(&self, end: &AstroTime, step_minutes: i64) -> impl Iterator<Item = AstroTime> {
+        let end = end.utc_millis();
+        let first = if step_minutes == 0 { None } else { Some(self.clone()) };
+
+        std::iter::successors(first, move |t| Some(t.plus_seconds(step_minutes * 60)))
+            .take_while(move |t| if step_minutes > 0 { t.utc_millis() <= end } else { t.utc_millis() >= end })
+    }
+
+/**
+ * Returns the seconds elapsed from `earlier` to this AstroTime, negative if `earlier` is in fact later.
+ * 
+ * Both are taken as instants, so they may use different timezones. The difference is counted in whole milliseconds
+ * of UTC, which keeps it exact where subtracting two Julian Times would round it to about 20 microseconds.
+ * 
+ * # Arguments
+ * * `earlier`: The start of the interval
+ **/
+    pub fn duration_since(&self, earlier: &AstroTime) -> f64 {
+        (self.utc_millis() - earlier.utc_millis()) as f64 / 1000.0
+    }
+
+    /// Seconds of the local clock counted from the start of the Julian period
+    fn local_seconds(&self) -> i64 {
+        self.julian_day_number() as i64 * 86400 + self.hour as i64 * 3600 + self.min as i64 * 60 + self.sec as i64
+    }
+
+    /// Milliseconds on the UTC clock counted from the start of the Julian period
+    fn utc_millis(&self) -> i64 {
+        (self.local_seconds() - (self.timezone as f64 * 3600.0).round() as i64) * 1000 + self.millis as i64
+    }
     
 }
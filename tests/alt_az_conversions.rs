@@ -1,4 +1,5 @@
-use astronav::coords::{dms_to_deg, hms_to_deg, star::AltAzBuilder};
+use astronav::approx::{approx_eq, Approx};
+use astronav::coords::{azimuth_to_compass, CoordParseError, dms_to_deg, hms_to_deg, observer::{celestial_pole_altitude, verify_polar_alignment, ObserverLocation}, star::{circumpolar_latitude, hour_angle_at_altitude, hour_angle_of_star, is_circumpolar, visibility_ha_range, parallactic_angle, sky_separation, AltAzBuilder, AltAzError}};
 
 #[test]
 fn test_decimal_inputs() {
@@ -54,3 +55,358 @@ fn test_non_decimal_inputs() {
     assert_eq!(130.98870686438966, alt.get_azimuth());
 }
 
+#[test]
+fn test_approx_comparisons() {
+    // Sirius
+    let alt = AltAzBuilder::new()
+        .dec(-16.75122)
+        .lat(12.45)
+        .lmst(199.05)
+        .ra(101.5504)
+        .seal()
+        .build();
+
+    assert!(approx_eq(-10.61319175, alt.get_altitude(), 1e-8));
+    assert!(!approx_eq(-10.6132, alt.get_altitude(), 1e-6));
+    assert_eq!(Approx::new(254.99376).tolerance(1e-5), alt.get_azimuth());
+    assert_ne!(Approx::new(254.99376), alt.get_azimuth());
+    assert_eq!(alt.get_azimuth(), Approx::new(254.99375998808006));
+}
+#[test]
+fn test_observer_location_matches_builder() {
+    let site = ObserverLocation::new(12.45, 80.2705);
+
+    // Fomalhaut, Sirius and Antares
+    for &(ra, dec, lst) in &[(344.745, -29.4925, 27.15), (101.5504, -16.75122, 199.05), (247.73, -26.4866, 200.875)] {
+        let expected = AltAzBuilder::new().dec(dec).lat(12.45).lmst(lst).ra(ra).seal().build();
+        let alt_az = site.altaz(ra, dec, lst);
+
+        assert_eq!(expected.get_altitude(), alt_az.get_altitude());
+        assert_eq!(expected.get_azimuth(), alt_az.get_azimuth());
+    }
+}
+
+#[test]
+fn test_observer_location_many_calls() {
+    let site = ObserverLocation::new(40.7128, -74.0060);
+
+    // Sweep the sidereal time over 100k steps, Vega must stay between its lowest and highest altitude
+    let (min_alt, max_alt) = (0..100_000)
+        .map(|i| site.altaz(279.2347, 38.7837, i as f64 * 0.0036).get_altitude())
+        .fold((f64::MAX, f64::MIN), |(lo, hi), alt| (lo.min(alt), hi.max(alt)));
+
+    assert!((max_alt - (90.0 - (40.7128 - 38.7837))).abs() < 1e-3);
+    assert!((min_alt - (40.7128 + 38.7837 - 90.0)).abs() < 1e-3);
+}
+
+
+#[cfg(feature = "noaa-sun")]
+mod observable {
+    use astronav::coords::star::{heliacal_rising_doy, is_observable_tonight};
+
+    #[test]
+    fn test_equatorial_star_in_winter() {
+        // Betelgeuse from New York
+        assert!(is_observable_tonight(88.7929, 7.4071, 40.7128, -74.0060, (2024, 1, 15), 30.0));
+        // Rigel from Chennai
+        assert!(is_observable_tonight(78.6335, -8.2041, 13.0843, 80.2705, (2024, 12, 31), 30.0));
+    }
+
+    #[test]
+    fn test_southern_star_from_northern_site() {
+        // Acrux from London never clears the horizon
+        assert!(!is_observable_tonight(186.6496, -63.0991, 51.5072, -0.1276, (2024, 1, 15), 0.0));
+    }
+
+    #[test]
+    fn test_star_behind_the_sun() {
+        // Antares is in conjunction with the Sun in early December
+        assert!(!is_observable_tonight(247.3519, -26.4320, 40.7128, -74.0060, (2024, 12, 1), 10.0));
+    }
+
+    #[test]
+    fn test_heliacal_rising() {
+        // Sirius from Athens, first seen at dawn in early August
+        let doy = heliacal_rising_doy(101.2872, -16.7161, -1.46, 37.9838, 23.7275, 2024).unwrap();
+        assert!((200..235).contains(&doy), "{}", doy);
+
+        // A fainter star needs a darker sky, so it is seen later
+        let faint = heliacal_rising_doy(101.2872, -16.7161, 2.0, 37.9838, 23.7275, 2024).unwrap();
+        assert!(faint > doy);
+
+        // Polaris never sets, so it never rises heliacally
+        assert_eq!(None, heliacal_rising_doy(37.9546, 89.2641, 1.98, 37.9838, 23.7275, 2024));
+
+        // There is no year before the year 0 to start from
+        assert_eq!(None, heliacal_rising_doy(101.2872, -16.7161, -1.46, 37.9838, 23.7275, 0));
+    }
+}
+
+#[test]
+fn test_azimuth_at_north_pole() {
+    // Polaris
+    let alt = AltAzBuilder::new()
+        .dec(89.2641)
+        .lat(90.0)
+        .lmst(120.0)
+        .ra(37.9529)
+        .seal()
+        .build();
+
+    assert!(approx_eq(89.2641, alt.get_altitude(), 1e-9));
+    assert_eq!(Err(AltAzError::ObserverAtPole), alt.try_get_azimuth());
+    assert_eq!(0.0, alt.get_azimuth());
+}
+
+#[test]
+fn test_azimuth_at_zenith() {
+    let alt = AltAzBuilder::new()
+        .dec(12.45)
+        .lat(12.45)
+        .lmst(101.5504)
+        .ra(101.5504)
+        .seal()
+        .build();
+
+    assert!(approx_eq(90.0, alt.get_altitude(), 1e-6));
+    assert_eq!(Err(AltAzError::ObjectAtZenith), alt.try_get_azimuth());
+    assert_eq!(0.0, alt.get_azimuth());
+}
+
+#[test]
+fn test_azimuth_to_compass() {
+    assert_eq!("N", azimuth_to_compass(0.0));
+    assert_eq!("NE", azimuth_to_compass(45.0));
+    assert_eq!("E", azimuth_to_compass(90.0));
+    assert_eq!("N", azimuth_to_compass(350.0));
+
+    assert_eq!("N", azimuth_to_compass(348.75));
+    assert_eq!("NNW", azimuth_to_compass(348.7));
+    assert_eq!("N", azimuth_to_compass(11.2));
+    assert_eq!("NNE", azimuth_to_compass(11.25));
+    assert_eq!("SW", azimuth_to_compass(225.0));
+    assert_eq!("W", azimuth_to_compass(-90.0));
+}
+
+#[test]
+fn test_signed_hour_angle() {
+    // (dec, lmst, ra, altitude, azimuth, hour angle) of Fomalhaut, Sirius and Antares
+    let stars = [
+        (-29.4925, 27.15, 344.745, 31.430612305028138, 223.46562682045789, 42.405),
+        (-16.75122, 199.05, 101.5504, -10.613191752481162, 254.99375998808006, 97.4996),
+        (-26.4866, 200.875, 247.73, 30.101068424513866, 130.98869628774506, -46.855),
+    ];
+
+    for (dec, lmst, ra, altitude, azimuth, hour_angle) in stars {
+        let alt = AltAzBuilder::new().dec(dec).lat(12.45).lmst(lmst).ra(ra).seal().build();
+
+        assert_eq!(altitude, alt.get_altitude());
+        assert_eq!(azimuth, alt.get_azimuth());
+        assert!(approx_eq(hour_angle, alt.get_hour_angle(), 1e-9));
+    }
+}
+
+#[test]
+fn test_sky_separation() {
+    let at = |dec: f64, lmst: f64| AltAzBuilder::new().dec(dec).lat(12.45).lmst(lmst).ra(100.0).seal().build();
+
+    // Altitude 30° towards the South and 30° above the Eastern horizon
+    let south = at(-47.55, 100.0);
+    let east = at(0.0, 100.0 - 60.0);
+    assert!(approx_eq(30.0, south.get_altitude(), 1e-9));
+    assert!(approx_eq(180.0, south.get_azimuth(), 1e-9));
+
+    // cos(sep) = sin(alt1)sin(alt2) + cos(alt1)cos(alt2)cos(az1 - az2)
+    let (alt1, az1) = (south.get_altitude().to_radians(), south.get_azimuth().to_radians());
+    let (alt2, az2) = (east.get_altitude().to_radians(), east.get_azimuth().to_radians());
+    let expected = (alt1.sin() * alt2.sin() + alt1.cos() * alt2.cos() * (az1 - az2).cos()).acos().to_degrees();
+
+    assert!(approx_eq(expected, sky_separation(&south, &east), 1e-9));
+    assert!(approx_eq(expected, sky_separation(&east, &south), 1e-9));
+    assert!(approx_eq(0.0, sky_separation(&east, &east), 1e-9));
+
+    // The zenith and a point on the meridian 30° lower
+    let zenith = at(12.45, 100.0);
+    let lower = at(42.45, 100.0);
+    assert!(approx_eq(30.0, sky_separation(&zenith, &lower), 1e-6));
+}
+
+#[test]
+fn test_celestial_pole_altitude() {
+    // The North celestial pole from +40° and the South celestial pole from -33°
+    assert_eq!(40.0, celestial_pole_altitude(40.0));
+    assert_eq!(33.0, celestial_pole_altitude(-33.0));
+
+    assert!(approx_eq(0.5, verify_polar_alignment(40.5, 40.0), 1e-12));
+    assert!(approx_eq(-0.2, verify_polar_alignment(32.8, -33.0), 1e-12));
+    assert_eq!(0.0, verify_polar_alignment(33.0, -33.0));
+}
+
+#[test]
+fn test_string_setters() {
+    // Antares
+    let alt = AltAzBuilder::new()
+        .dec_dms("-26:29:11.8")
+        .unwrap()
+        .lat(dms_to_deg("12:27:0").unwrap())
+        .lmst(hms_to_deg("13:23:30").unwrap())
+        .ra_hms("16:30:55.2")
+        .unwrap()
+        .seal()
+        .build();
+
+    assert_eq!(30.10106212143597, alt.get_altitude());
+    assert_eq!(130.98870686438966, alt.get_azimuth());
+
+    assert_eq!(Some(CoordParseError::WrongComponentCount(2)), AltAzBuilder::new().ra_hms("16:30").err());
+    assert_eq!(Some(CoordParseError::InvalidNumber("x".to_owned())), AltAzBuilder::new().dec_dms("-26:x:11.8").err());
+    assert_eq!(Some(CoordParseError::InvalidNumber("x".to_owned())), AltAzBuilder::new().ra_hms("16:x:55.2").err());
+    assert_eq!(Some(CoordParseError::Empty), AltAzBuilder::new().ra_hms(" ").err());
+}
+
+#[test]
+fn test_is_close_across_north() {
+    // A star crossing the meridian below the pole, just either side of North
+    let at = |lmst: f64| AltAzBuilder::new().dec(60.0).lat(40.0).lmst(lmst).ra(0.0).seal().build();
+    let west_of_north = at(179.9);
+    let east_of_north = at(180.1);
+
+    assert!(east_of_north.get_azimuth() < 1.0);
+    assert!(west_of_north.get_azimuth() > 359.0);
+    assert!(east_of_north.is_close(&west_of_north, 0.5));
+    assert!(!east_of_north.is_close(&west_of_north, 0.001));
+
+    assert!(!east_of_north.is_close(&at(170.0), 0.5));
+}
+
+#[test]
+fn test_hour_angle_at_altitude() {
+    // Antares rising and setting at latitude 12.45°
+    let ha = hour_angle_at_altitude(-26.4866, 12.45, 0.0).unwrap();
+    // cos H = -tan(dec) tan(lat) on the horizon
+    let expected = (-(-26.4866_f64).to_radians().tan() * 12.45_f64.to_radians().tan()).acos().to_degrees();
+    assert!(approx_eq(expected, ha, 1e-9));
+
+    for h in [-ha, ha] {
+        let alt = AltAzBuilder::new().dec(-26.4866).lat(12.45).lmst(247.73 + h).ra(247.73).seal().build();
+        assert!(approx_eq(0.0, alt.get_altitude(), 1e-9));
+    }
+
+    // A star 10° north of the equator peaks at 40° seen from latitude 60°
+    assert_eq!(None, hour_angle_at_altitude(10.0, 60.0, 45.0));
+    assert!(hour_angle_at_altitude(10.0, 60.0, 39.0).is_some());
+    // Circumpolar, so it never sets
+    assert_eq!(None, hour_angle_at_altitude(80.0, 60.0, 0.0));
+}
+
+#[test]
+fn test_apparent_azimuth() {
+    // A star setting in the west, 2° above the horizon at latitude 60°
+    let ha = hour_angle_at_altitude(20.0, 60.0, 2.0).unwrap();
+    let low = AltAzBuilder::new().dec(20.0).lat(60.0).lmst(100.0 + ha).ra(100.0).seal().build();
+    assert!(approx_eq(2.0, low.get_altitude(), 1e-9));
+
+    // Refraction lifts it by about 0.3°
+    let lift = low.get_apparent_altitude() - low.get_altitude();
+    assert!(lift > 0.25 && lift < 0.35);
+
+    // Seen higher, the star is placed a little closer to the meridian
+    let shift = low.get_azimuth() - low.get_apparent_azimuth();
+    assert!(shift > 0.0 && shift < 1.0);
+
+    // High up the refraction and the azimuth shift nearly vanish
+    let high = AltAzBuilder::new().dec(20.0).lat(60.0).lmst(130.0).ra(100.0).seal().build();
+    assert!(high.get_altitude() > 40.0);
+    assert!((high.get_azimuth() - high.get_apparent_azimuth()).abs() < shift / 10.0);
+}
+
+#[test]
+fn test_parallactic_angle() {
+    // Antares from latitude 12.45° passes the meridian south of the zenith
+    let at = |lst: f64| AltAzBuilder::new().dec(-26.4866).lat(12.45).lmst(lst).ra(247.73).seal().build();
+
+    assert!(at(247.73).get_parallactic_angle().abs() < 1e-9);
+    assert!(at(217.73).get_parallactic_angle() < 0.0);
+    assert!(at(277.73).get_parallactic_angle() > 0.0);
+    assert!(approx_eq(at(277.73).get_parallactic_angle(), parallactic_angle(30.0, -26.4866, 12.45), 1e-9));
+
+    // Seen from the equator, a body rising due east has the pole at a right angle from its zenith direction
+    assert!(approx_eq(-90.0, parallactic_angle(-90.0, 0.0, 0.0), 1e-9));
+}
+
+#[test]
+fn test_offset() {
+    // Just west of North, nudged 2° further round
+    let star = AltAzBuilder::new().dec(60.0).lat(40.0).lmst(179.5).ra(0.0).seal().build();
+    assert!(star.get_azimuth() > 359.0);
+
+    let nudged = star.offset(0.0, 2.0);
+    assert!(approx_eq((star.get_azimuth() + 2.0) - 360.0, nudged.get_azimuth(), 1e-9));
+    assert!(approx_eq(star.get_altitude(), nudged.get_altitude(), 1e-9));
+
+    // And back the other way across North
+    let back = nudged.offset(0.5, -2.0);
+    assert!(back.is_close(&star.offset(0.5, 0.0), 1e-9));
+    assert!(approx_eq(star.get_altitude() + 0.5, back.get_altitude(), 1e-9));
+
+    // Pushed past the zenith, the altitude stops there
+    let high = AltAzBuilder::new().dec(30.0).lat(40.0).lmst(100.0).ra(100.0).seal().build();
+    assert!(approx_eq(80.0, high.get_altitude(), 1e-9));
+    let zenith = high.offset(15.0, 0.0);
+    assert!(approx_eq(90.0, zenith.get_altitude(), 1e-6));
+    assert_eq!(Err(AltAzError::ObjectAtZenith), zenith.try_get_azimuth());
+}
+
+#[test]
+fn test_circumpolar_latitude() {
+    // A star at +70° never sets from 20°N northwards
+    let lat = circumpolar_latitude(70.0);
+    assert!(approx_eq(20.0, lat, 1e-12));
+    assert!(lat > 0.0);
+
+    assert!(is_circumpolar(70.0, lat));
+    assert!(is_circumpolar(70.0, 45.0));
+    assert!(!is_circumpolar(70.0, lat - 0.1));
+    assert!(!is_circumpolar(70.0, -45.0));
+    // It also stays up all day by hour_angle_at_altitude
+    assert_eq!(None, hour_angle_at_altitude(70.0, 20.1, 0.0));
+
+    // And the same in the south
+    assert!(approx_eq(-20.0, circumpolar_latitude(-70.0), 1e-12));
+    assert!(is_circumpolar(-70.0, -30.0));
+    assert!(!is_circumpolar(-70.0, 30.0));
+}
+
+#[test]
+fn test_hour_angle_of_star() {
+    use astronav::time::AstroTime;
+
+    let time = AstroTime::new(17, 5, 2024, 21, 0, 0, 5.5);
+    let lst = time.lmst_in_degrees(80.2705);
+
+    // On the meridian
+    assert!(hour_angle_of_star(lst, 80.2705, &time).abs() < 1e-9);
+
+    // The same hour angle as the AltAz, either side of the meridian and across 0° of Right Ascension
+    for ra in [lst - 100.0, lst + 30.0, (lst + 179.0).rem_euclid(360.0), 0.5, 359.5] {
+        let alt = AltAzBuilder::new().dec(-20.0).lat(13.0843).lmst(lst).ra(ra).seal().build();
+        assert!(approx_eq(alt.get_hour_angle(), hour_angle_of_star(ra, 80.2705, &time), 1e-9));
+    }
+}
+
+#[test]
+fn test_visibility_ha_range() {
+    // From latitude 50°, a star at +50° passes the zenith and stays above 30° for most of the day,
+    // while one at -9° barely clears 30° at its transit
+    let (from, to) = visibility_ha_range(50.0, 50.0, 30.0).unwrap();
+    assert!(approx_eq(-from, to, 1e-12));
+    assert!(to > 100.0);
+
+    let (_, narrow) = visibility_ha_range(-9.0, 50.0, 30.0).unwrap();
+    assert!(narrow < 15.0);
+    assert!(approx_eq(narrow, hour_angle_at_altitude(-9.0, 50.0, 30.0).unwrap(), 1e-12));
+
+    // Always above, never above
+    assert_eq!(Some((-180.0, 180.0)), visibility_ha_range(80.0, 50.0, 10.0));
+    assert_eq!(None, visibility_ha_range(-20.0, 50.0, 30.0));
+}
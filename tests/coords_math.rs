@@ -0,0 +1,70 @@
+use astronav::coords::math::{find_root, to_cartesian, to_spherical, Mat3, Vec3};
+
+fn assert_vec_eq(a: Vec3, b: Vec3) {
+    assert!((a.x - b.x).abs() < 1e-12, "{:?} != {:?}", a, b);
+    assert!((a.y - b.y).abs() < 1e-12, "{:?} != {:?}", a, b);
+    assert!((a.z - b.z).abs() < 1e-12, "{:?} != {:?}", a, b);
+}
+
+#[test]
+fn test_rotation_composition() {
+    let v = to_cartesian(101.2875, -16.7161);
+
+    // Two rotations about the same axis add up
+    assert_vec_eq(Mat3::rotate_z(30.0) * (Mat3::rotate_z(15.0) * v), Mat3::rotate_z(45.0) * v);
+    assert_vec_eq((Mat3::rotate_x(10.0) * Mat3::rotate_x(13.5)) * v, Mat3::rotate_x(23.5) * v);
+
+    // A rotation followed by its transpose is the identity
+    let r = Mat3::rotate_z(192.85948) * Mat3::rotate_y(62.87175) * Mat3::rotate_x(-23.4392911);
+    assert_vec_eq(r.transpose() * (r * v), v);
+    assert_vec_eq((r * r.transpose()) * v, Mat3::identity() * v);
+
+    // Right handed rotations of the basis vectors
+    assert_vec_eq(Mat3::rotate_z(90.0) * Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+    assert_vec_eq(Mat3::rotate_x(90.0) * Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+    assert_vec_eq(Mat3::rotate_y(90.0) * Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_sphere_round_trip() {
+    for &(ra, dec) in &[(0.0, 0.0), (101.2875, -16.7161), (279.2347, 38.7837), (37.9546, 89.2641), (359.9, -89.9)] {
+        let v = to_cartesian(ra, dec);
+        assert!((v.norm() - 1.0).abs() < 1e-12);
+
+        let (ra2, dec2) = to_spherical(v);
+        assert!((ra - ra2).abs() < 1e-9);
+        assert!((dec - dec2).abs() < 1e-9);
+    }
+
+    // Lengths other than one do not change the direction
+    let (ra, dec) = to_spherical(Vec3::new(0.0, -2.0, 2.0));
+    assert!((ra - 270.0).abs() < 1e-9);
+    assert!((dec - 45.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_find_root_on_sine() {
+    // The sine crosses zero going down at PI and going up at 2 PI
+    let x = find_root(f64::sin, 2.0, 4.0, 1e-12).unwrap();
+    assert!((x - std::f64::consts::PI).abs() < 1e-12);
+    let x = find_root(f64::sin, 5.0, 7.0, 1e-12).unwrap();
+    assert!((x - 2.0 * std::f64::consts::PI).abs() < 1e-12);
+
+    // The ends may come in either order
+    let x = find_root(|x: f64| (x / 10.0).sin() - 0.5, 20.0, 0.0, 1e-9).unwrap();
+    assert!((x - 10.0 * (0.5_f64).asin()).abs() < 1e-9);
+
+    // A coarse tolerance stops early, but still within it
+    let x = find_root(f64::sin, 2.0, 4.0, 0.01).unwrap();
+    assert!((x - std::f64::consts::PI).abs() <= 0.01);
+
+    // A root at an end of the bracket
+    assert_eq!(Some(0.0), find_root(f64::sin, 0.0, 1.0, 1e-9));
+
+    // No sign change, no root
+    assert_eq!(None, find_root(f64::sin, 0.5, 2.5, 1e-9));
+
+    // A step function still converges on its jump
+    let x = find_root(|x: f64| if x < 1.234 { -1.0 } else { 1.0 }, 0.0, 10.0, 1e-9).unwrap();
+    assert!((x - 1.234).abs() < 1e-9);
+}
@@ -0,0 +1,167 @@
+use astronav::coords::moon::{
+    moon_age_days, moon_ascending_node_longitude, moon_azimuth_at, moon_phase_calendar, moonrise_azimuth, moonset_azimuth,
+    MoonPosition, SYNODIC_MONTH_DAYS,
+};
+use astronav::coords::observer::{geodetic_to_geocentric_latitude, ObserverLocation};
+use astronav::time::AstroTime;
+
+#[test]
+fn test_meeus_example() {
+    // April 12th 1992, 0h (Meeus, Example 47.a)
+    let moon = MoonPosition::new().date(1992, 4, 12).timezone(0.0);
+
+    assert!((moon.ecliptic_longitude() - 133.162655).abs() < 0.02);
+    assert!((moon.ecliptic_latitude() - -3.229126).abs() < 0.01);
+    assert!((moon.distance_in_km() - 368409.7).abs() < 50.0);
+}
+
+#[test]
+fn test_ecliptic_latitude_at_eclipses() {
+    // Total solar eclipse of April 8th 2024, greatest eclipse at 18:17 UT
+    let moon = MoonPosition::new().date(2024, 4, 8).hour(18).min(17).timezone(0.0);
+    assert!(moon.ecliptic_latitude().abs() < 0.5);
+
+    // Total lunar eclipse of November 8th 2022, greatest eclipse at 10:59 UT
+    let moon = MoonPosition::new().date(2022, 11, 8).hour(10).min(59).timezone(0.0);
+    assert!(moon.ecliptic_latitude().abs() < 0.5);
+
+    // The new moon of January 11th 2024 passed well clear of the Sun
+    let moon = MoonPosition::new().date(2024, 1, 11).hour(11).min(57).timezone(0.0);
+    assert!(moon.ecliptic_latitude().abs() > 1.5);
+}
+
+#[test]
+fn test_moon_age() {
+    // Half an hour after the new moon of January 11th 2024 at 11:57 UT
+    let new_moon = AstroTime::new(11, 1, 2024, 12, 27, 0, 0.0);
+    assert!((moon_age_days(&new_moon) - 0.02).abs() < 0.03);
+
+    // Full moon of January 25th 2024 at 17:54 UT. Half a mean lunation is 14.77 days,
+    // but the true Moon runs ahead of the mean one in this lunation
+    let full_moon = AstroTime::new(25, 1, 2024, 17, 54, 0, 0.0);
+    assert!((moon_age_days(&full_moon) - 14.25).abs() < 0.05);
+    assert!((MoonPosition::from(&full_moon).elongation() - 180.0).abs() < 0.1);
+
+    // Just before the next new moon of February 9th 2024 at 22:59 UT
+    let old_moon = AstroTime::new(9, 2, 2024, 20, 0, 0, 0.0);
+    let age = moon_age_days(&old_moon);
+    assert!(age > 28.0 && age < SYNODIC_MONTH_DAYS + 0.5);
+}
+
+#[test]
+fn test_moon_phase_calendar() {
+    // January 2024: last quarter on the 4th, new moon on the 11th, first quarter on the 18th, full moon on the 25th (UT)
+    let calendar = moon_phase_calendar(2024, 1);
+    assert_eq!(31, calendar.len());
+    assert!(calendar.iter().enumerate().all(|(i, day)| day.0 as usize == i + 1));
+
+    let full = calendar[24];
+    assert_eq!("Full Moon", full.2);
+    assert!(full.1 > 0.99);
+
+    let new = calendar[10];
+    assert_eq!("New Moon", new.2);
+    assert!(new.1 < 0.01);
+
+    assert_eq!("Last Quarter", calendar[3].2);
+    assert_eq!("First Quarter", calendar[17].2);
+    assert!((calendar[17].1 - 0.5).abs() < 0.1);
+
+    // Each principal phase shows up on one day only
+    for phase in ["New Moon", "First Quarter", "Full Moon", "Last Quarter"] {
+        assert_eq!(1, calendar.iter().filter(|day| day.2 == phase).count());
+    }
+    assert_eq!("Waxing Gibbous", calendar[20].2);
+    assert_eq!("Waning Crescent", calendar[7].2);
+
+    // Leap February
+    assert_eq!(29, moon_phase_calendar(2024, 2).len());
+    assert_eq!(31, moon_phase_calendar(2024, 12).len());
+}
+
+#[test]
+fn test_moon_topocentric_position() {
+    // The parallax lowers the Moon by almost a degree on the horizon, and not at all at the zenith
+    let time = AstroTime::new(25, 1, 2024, 17, 0, 0, -5.0);
+    let moon = MoonPosition::from(&time);
+    let (ra, dec) = moon.equatorial();
+
+    let new_york = ObserverLocation::new(40.7128, -74.0060);
+    let lst = time.lmst_in_degrees(new_york.long());
+    let (topo_ra, topo_dec) = moon.topocentric_equatorial(&new_york, lst);
+    let geocentric = new_york.altaz(ra, dec, lst).get_altitude();
+    let topocentric = new_york.altaz(topo_ra, topo_dec, lst).get_altitude();
+    assert!(geocentric - topocentric > 0.8 && geocentric - topocentric < 1.0);
+
+    // Right below the Moon is where the geocentric latitude equals its declination
+    let mut lat = dec;
+    for _ in 0..3 {
+        lat += dec - geodetic_to_geocentric_latitude(lat, 0.0);
+    }
+    let below = ObserverLocation::new(lat, 0.0);
+    let (_, zenith_dec) = moon.topocentric_equatorial(&below, ra);
+    assert!((zenith_dec - dec).abs() < 1e-6);
+}
+
+#[test]
+fn test_geocentric_latitude() {
+    // No correction at the equator and the poles, and the largest around ±45°, at about 11.5′
+    assert_eq!(0.0, geodetic_to_geocentric_latitude(0.0, 0.0));
+    assert!((geodetic_to_geocentric_latitude(90.0, 0.0) - 90.0).abs() < 1e-9);
+    assert!((geodetic_to_geocentric_latitude(-90.0, 0.0) + 90.0).abs() < 1e-9);
+
+    let correction = |lat: f64| lat - geodetic_to_geocentric_latitude(lat, 0.0);
+    assert!((correction(45.0) * 60.0 - 11.54).abs() < 0.05);
+    assert!((correction(-45.0) + correction(45.0)).abs() < 1e-12);
+    assert!([10.0, 30.0, 44.0, 46.0, 60.0, 80.0].iter().all(|lat| correction(*lat) < correction(45.0)));
+
+    // Up a mountain the observer is further from the center, along the vertical of the geodetic latitude
+    let summit = ObserverLocation::new(45.0, 0.0).with_elevation(4000.0);
+    let (rho_sin, rho_cos) = summit.geocentric_sin_cos();
+    let (sea_sin, sea_cos) = ObserverLocation::new(45.0, 0.0).geocentric_sin_cos();
+    assert!(rho_sin.hypot(rho_cos) > sea_sin.hypot(sea_cos));
+    assert!(geodetic_to_geocentric_latitude(45.0, 4000.0) > geodetic_to_geocentric_latitude(45.0, 0.0));
+}
+
+#[test]
+fn test_moonrise_azimuth() {
+    let new_york = ObserverLocation::new(40.7128, -74.0060);
+    let on = |day: u8, month: u8| AstroTime::new(day, month, 2024, 0, 0, 0, -5.0);
+
+    // The full moon of January 25th rises north of east, as the Moon is then far north of the equator
+    let january = moonrise_azimuth(&on(25, 1), &new_york).unwrap();
+    assert!(january < 65.0);
+
+    // Near the June solstice the full moon sits in the south, so it rises south of east
+    let june = moonrise_azimuth(&on(21, 6), &new_york).unwrap();
+    assert!(june > 115.0);
+
+    // Two weeks from the full moon of January the Moon is far south, and rises south of east
+    assert!(moonrise_azimuth(&on(8, 2), &new_york).unwrap() > 115.0);
+
+    // It sets mirrored about the meridian from where it rose, give or take its motion in declination
+    let set = moonset_azimuth(&on(26, 1), &new_york).unwrap();
+    assert!((set - (360.0 - january)).abs() < 5.0);
+
+    // At the rise the Moon is on the horizon in the computed direction
+    let azimuth = moon_azimuth_at(&AstroTime::new(25, 1, 2024, 16, 50, 0, -5.0), &new_york);
+    assert!((azimuth - january).abs() < 2.0);
+}
+
+#[test]
+fn test_moon_ascending_node() {
+    let jt = |year: u16| AstroTime::new(1, 1, year, 0, 0, 0, 0.0).julian_time_tt();
+
+    // The node regresses by 19.34° a year on average, give or take its swing about the mean node
+    let yearly = (moon_ascending_node_longitude(jt(2024)) - moon_ascending_node_longitude(jt(2025))).rem_euclid(360.0);
+    assert!((yearly - 19.3).abs() < 3.0);
+
+    let decade = (moon_ascending_node_longitude(jt(2000)) - moon_ascending_node_longitude(jt(2010))).rem_euclid(360.0);
+    assert!((decade / 10.0 - 19.34).abs() < 0.3);
+
+    // The Moon's argument of latitude is its distance from the node
+    let moon = MoonPosition::new().date(2024, 4, 8).hour(18).min(17).timezone(0.0);
+    let node = moon_ascending_node_longitude(AstroTime::new(8, 4, 2024, 18, 17, 0, 0.0).julian_time_tt());
+    let from_node = (moon.ecliptic_longitude() - node).rem_euclid(360.0);
+    assert!(!(5.0..=355.0).contains(&from_node));
+}
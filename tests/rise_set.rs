@@ -0,0 +1,114 @@
+use astronav::coords::moon::MoonPosition;
+use astronav::coords::observer::ObserverLocation;
+use astronav::coords::rise_set::rise_set_iterative;
+use astronav::time::AstroTime;
+
+#[test]
+fn test_star_rise_and_set() {
+    // Sirius from Chennai, up for about 11.5 hours and 4 minutes earlier each day
+    let chennai = ObserverLocation::new(13.0843, 80.2705);
+    let sirius = |_: &AstroTime| (101.2872, -16.7161);
+
+    let date = AstroTime::new(17, 5, 2024, 0, 0, 0, 5.5);
+    let (rise, set) = rise_set_iterative(sirius, -0.5667, &date, &chennai);
+    let (rise, set) = (rise.unwrap(), set.unwrap());
+    let up_hours = (set.julian_time() - rise.julian_time()) * 24.0;
+    assert!((up_hours - 11.5).abs() < 0.1);
+
+    let next = rise_set_iterative(sirius, -0.5667, &date.plus_seconds(86400), &chennai).0.unwrap();
+    let earlier_mins = 1440.0 - (next.julian_time() - rise.julian_time()) * 1440.0;
+    assert!((earlier_mins - 3.93).abs() < 0.05);
+
+    // Any time of the day gives the same answer
+    let later = AstroTime::new(17, 5, 2024, 15, 30, 0, 5.5);
+    let again = rise_set_iterative(sirius, -0.5667, &later, &chennai).0.unwrap();
+    assert_eq!(rise.julian_time(), again.julian_time());
+
+    // Acrux never rises from London
+    let london = ObserverLocation::new(51.5072, -0.1276);
+    let (rise, set) = rise_set_iterative(|_| (186.6496, -63.0991), -0.5667, &date, &london);
+    assert!(rise.is_none() && set.is_none());
+}
+
+#[test]
+fn test_moonrise() {
+    // The full moon of January 25th 2024 rises over New York around sunset, which is at 17:03
+    let new_york = ObserverLocation::new(40.7128, -74.0060);
+    let date = AstroTime::new(25, 1, 2024, 0, 0, 0, -5.0);
+    let moon = |t: &AstroTime| MoonPosition::from(t).equatorial();
+
+    let (rise, set) = rise_set_iterative(moon, 0.125, &date, &new_york);
+    let rise = rise.unwrap();
+    assert_eq!(16, rise.hour);
+    assert!(rise.min > 40);
+
+    // and sets after sunrise, which is at 7:12
+    let set = set.unwrap();
+    assert_eq!(7, set.hour);
+    assert!(set.min > 12 && set.min < 45);
+}
+
+#[cfg(feature = "noaa-sun")]
+mod noaa_sun {
+    use astronav::coords::almanac::annual_sun_moon_table;
+    use astronav::coords::moon::MoonPosition;
+    use astronav::coords::noaa_sun::{solar_day_events, NOAASun};
+    use astronav::coords::observer::ObserverLocation;
+    use astronav::coords::rise_set::rise_set_iterative;
+    use astronav::time::AstroTime;
+
+    #[test]
+    fn test_matches_the_closed_form_sun() {
+        let sun = |t: &AstroTime| {
+            let sun = NOAASun::from(t);
+            (sun.ra_in_deg(), sun.declination() as f64)
+        };
+        let mins_of_day = |t: &AstroTime| t.hour as f64 * 60.0 + t.min as f64 + t.sec as f64 / 60.0;
+
+        for (lat, long, timezone, date) in [
+            (13.0843, 80.2705, 5.5, (17, 5, 2024)),
+            (40.7128, -74.0060, -5.0, (15, 1, 2024)),
+            (-33.8688, 151.2093, 10.0, (21, 6, 2024)),
+        ] {
+            let observer = ObserverLocation::new(lat, long);
+            let date = AstroTime::new(date.0, date.1, date.2, 0, 0, 0, timezone);
+            let (rise, set) = rise_set_iterative(sun, -0.8333, &date, &observer);
+
+            // The closed form takes the declination at midnight rather than at the event,
+            // and NOAASun steps the hour angle by whole minutes
+            let noaa = NOAASun::from(&date).lat(lat as f32).long(long as f32);
+            assert!((mins_of_day(&rise.unwrap()) - noaa.sunrise_time_mins()).abs() < 1.5);
+            assert!((mins_of_day(&set.unwrap()) - noaa.sunset_time_mins()).abs() < 1.5);
+        }
+    }
+
+    #[test]
+    fn test_annual_sun_moon_table() {
+        let new_york = ObserverLocation::new(40.7128, -74.0060);
+        let table = annual_sun_moon_table(&new_york, -5.0, 2024);
+        assert_eq!(366, table.len());
+        assert_eq!(365, annual_sun_moon_table(&new_york, -5.0, 2023).len());
+        assert_eq!((2024, 12, 31), table[365].date);
+
+        // January 25th, the full moon
+        let day = &table[24];
+        assert_eq!((2024, 1, 25), day.date);
+
+        let sun = solar_day_events(&ObserverLocation::new(40.7128, -74.0060), -5.0, (2024, 1, 25));
+        assert_eq!(sun.sunrise, day.sunrise);
+        assert_eq!(sun.noon, Some(day.transit));
+        assert_eq!(sun.sunset, day.sunset);
+
+        let date = AstroTime::new(25, 1, 2024, 0, 0, 0, -5.0);
+        let moon = |t: &AstroTime| MoonPosition::from(t).equatorial();
+        let (rise, set) = rise_set_iterative(moon, 0.125, &date, &new_york);
+        let hours = |t: AstroTime| t.hour as f64 + t.min as f64 / 60.0 + t.sec as f64 / 3600.0;
+        assert!((day.moonrise.unwrap() - hours(rise.unwrap())).abs() < 2.0 / 3600.0);
+        assert!((day.moonset.unwrap() - hours(set.unwrap())).abs() < 2.0 / 3600.0);
+        assert!(day.moon_illuminated_fraction > 0.99);
+
+        // The Moon rises about 50 minutes later each day, so some days have no moonrise
+        assert!(table.iter().any(|day| day.moonrise.is_none()));
+        assert!(table.iter().filter(|day| day.moonrise.is_none()).count() < 15);
+    }
+}
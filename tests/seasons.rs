@@ -0,0 +1,32 @@
+use astronav::coords::seasons::{equinoxes_and_solstices, season, Hemisphere, Season};
+use astronav::time::day_of_year;
+
+#[test]
+fn test_equinoxes_and_solstices() {
+    // 2024: March 20th 03:06, June 20th 20:51, September 22nd 12:44 and December 21st 09:21 UT
+    let expected = [(3, 20, 3, 6), (6, 20, 20, 51), (9, 22, 12, 44), (12, 21, 9, 21)];
+
+    for (event, (month, day, hour, min)) in equinoxes_and_solstices(2024).iter().zip(expected) {
+        assert_eq!((2024, month, day), (event.year, event.month, event.day));
+        let mins = (event.hour as i32 * 60 + event.min as i32) - (hour * 60 + min);
+        assert!(mins.abs() < 30);
+    }
+}
+
+#[test]
+fn test_season() {
+    let late_june = day_of_year(2024, 6, 25);
+    assert_eq!(Season::Summer, season(2024, late_june, Hemisphere::Northern));
+    assert_eq!(Season::Winter, season(2024, late_june, Hemisphere::Southern));
+
+    // The seasons turn on the day of the equinox or the solstice
+    assert_eq!(Season::Winter, season(2024, day_of_year(2024, 3, 19), Hemisphere::Northern));
+    assert_eq!(Season::Spring, season(2024, day_of_year(2024, 3, 20), Hemisphere::Northern));
+    assert_eq!(Season::Autumn, season(2024, day_of_year(2024, 10, 1), Hemisphere::Northern));
+    assert_eq!(Season::Spring, season(2024, day_of_year(2024, 10, 1), Hemisphere::Southern));
+
+    // Winter runs across the new year
+    assert_eq!(Season::Winter, season(2024, 1, Hemisphere::Northern));
+    assert_eq!(Season::Winter, season(2024, 366, Hemisphere::Northern));
+    assert_eq!(Season::Summer, season(2024, 1, Hemisphere::of_latitude(-33.8688)));
+}
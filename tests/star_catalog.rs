@@ -0,0 +1,92 @@
+use astronav::coords::frames::{angular_separation, equatorial_midpoint, equatorial_to_galactic, galactic_to_equatorial};
+use astronav::coords::star::catalog::{brighter_than, nearest_catalog_star, BRIGHT_STARS};
+
+#[test]
+fn test_brighter_than() {
+    let names: Vec<&str> = brighter_than(1.0).iter().map(|s| s.name).collect();
+
+    assert!(names.contains(&"Sirius"));
+    assert!(names.contains(&"Canopus"));
+    assert!(!names.contains(&"Polaris"));
+    assert!(brighter_than(1.0).iter().all(|s| s.magnitude < 1.0));
+
+    assert_eq!(BRIGHT_STARS.len(), brighter_than(2.0).len());
+    assert!(brighter_than(-2.0).is_empty());
+}
+
+#[test]
+fn test_catalog_fields() {
+    let sirius = BRIGHT_STARS[0];
+
+    assert_eq!("Sirius", sirius.name);
+    assert_eq!(-1.46, sirius.magnitude);
+    assert_eq!("A1V", sirius.spectral_type);
+    assert!(BRIGHT_STARS.windows(2).all(|w| w[0].magnitude <= w[1].magnitude));
+}
+
+#[test]
+fn test_galactic_coordinates() {
+    let arcturus = BRIGHT_STARS.iter().find(|s| s.name == "Arcturus").unwrap();
+    let (l, b) = arcturus.galactic();
+
+    // Arcturus lies high above the galactic plane
+    assert!((l - 15.1).abs() < 0.1);
+    assert!((b - 69.11).abs() < 0.05);
+
+    // Deneb lies in the Milky Way
+    let deneb = BRIGHT_STARS.iter().find(|s| s.name == "Deneb").unwrap();
+    assert!(deneb.galactic().1.abs() < 5.0);
+
+    // The origin of the galactic frame
+    let (l, b) = equatorial_to_galactic(266.40499, -28.93617);
+    assert!(l < 1e-4 && b.abs() < 1e-4);
+
+    let (ra, dec) = galactic_to_equatorial(l, b);
+    assert!((ra - 266.40499).abs() < 1e-9 && (dec + 28.93617).abs() < 1e-9);
+}
+
+#[test]
+fn test_nearest_catalog_star() {
+    // Pointing a little off Vega
+    let (star, sep) = nearest_catalog_star(279.3, 38.9);
+    assert_eq!(star.name, "Vega");
+    assert!(sep < 0.2);
+    assert_eq!(sep, angular_separation(279.3, 38.9, star.ra_in_deg(), star.dec_in_deg()));
+
+    // Exactly on a catalog star
+    let sirius = BRIGHT_STARS[0];
+    let (star, sep) = nearest_catalog_star(sirius.ra_in_deg(), sirius.dec_in_deg());
+    assert_eq!(star.name, "Sirius");
+    assert!(sep < 1e-6);
+
+    // Separation across the 0/360 RA seam
+    assert!((angular_separation(359.5, 0.0, 0.5, 0.0) - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_equatorial_midpoint() {
+    // Symmetric about the equator
+    let (ra, dec) = equatorial_midpoint(83.8, 20.0, 83.8, -20.0);
+    assert!((ra - 83.8).abs() < 1e-9);
+    assert!(dec.abs() < 1e-9);
+
+    // Across the 0/360 RA seam
+    let (ra, dec) = equatorial_midpoint(359.0, 10.0, 1.0, 10.0);
+    assert!(angular_separation(ra, 0.0, 0.0, 0.0) < 1e-9);
+    assert!(dec > 10.0);
+
+    // Equidistant from both ends
+    let (ra, dec) = equatorial_midpoint(100.0, 30.0, 140.0, -5.0);
+    let d1 = angular_separation(ra, dec, 100.0, 30.0);
+    let d2 = angular_separation(ra, dec, 140.0, -5.0);
+    assert!((d1 - d2).abs() < 1e-9);
+    assert!((d1 + d2 - angular_separation(100.0, 30.0, 140.0, -5.0)).abs() < 1e-9);
+
+    // Antipodal points have a midpoint 90° away from both
+    for (ra1, dec1) in [(10.0, 25.0), (200.0, -40.0), (0.0, 0.0)] {
+        let (ra2, dec2) = ((ra1 + 180.0) % 360.0, -dec1);
+        let (ra, dec) = equatorial_midpoint(ra1, dec1, ra2, dec2);
+        assert!((angular_separation(ra, dec, ra1, dec1) - 90.0).abs() < 1e-9);
+        assert!((angular_separation(ra, dec, ra2, dec2) - 90.0).abs() < 1e-9);
+    }
+}
@@ -1,4 +1,4 @@
-use astronav::coords::{hours_to_hms, sun::SunRiseAndSet};
+use astronav::coords::{hours_to_hms, sun::{equation_of_center, sun_ecliptic_longitude, sun_horizontal_parallax, summer_solstice_noon_altitude, winter_solstice_noon_altitude, LimbReference, SunMood, SunRiseAndSet}, InvalidLatitude};
 
 #[test]
 fn test_sun_rise_in_new_york() {
@@ -8,6 +8,7 @@ fn test_sun_rise_in_new_york() {
         long: -74.0060,
         lat: 40.7128,
         timezone: -4.0,
+        ..Default::default()
     };
 
     let sma = sun_new_york.sunrise_mean_anomaly();
@@ -40,6 +41,7 @@ fn test_sun_set_in_new_york() {
         long: -74.0060,
         lat: 40.7128,
         timezone: -4.0,
+        ..Default::default()
     };
 
     let sma = sun_new_york.sunset_mean_anomaly();
@@ -68,7 +70,7 @@ fn test_sun_set_in_new_york() {
 fn test_sun_set_in_new_york_using_setters() {
     // May 16th 2024
     let sun_new_york = SunRiseAndSet::new()
-        .date(2024, 05, 16)
+        .date(2024, 5, 16)
         .long(-74.0060)
         .lat(40.7128)
         .timezone(-4.0);
@@ -100,7 +102,7 @@ fn test_sun_set_in_new_york_using_setters() {
 fn test_day_length_new_york() {
     // May 16th 2024
     let sun_new_york = SunRiseAndSet::new()
-        .date(2024, 05, 16)
+        .date(2024, 5, 16)
         .long(-74.0060)
         .lat(40.7128)
         .timezone(-4.0);
@@ -114,9 +116,251 @@ fn test_day_length_new_york() {
 }
 
 
+#[test]
+fn test_current_hour_angle_at_solar_noon_new_york() {
+    // May 16th 2024
+    let sun_new_york = SunRiseAndSet::new()
+        .date(2024, 5, 16)
+        .long(-74.0060)
+        .lat(40.7128)
+        .timezone(-4.0);
+
+    let solar_noon = (sun_new_york.sunrise_time().unwrap() + sun_new_york.sunset_time().unwrap()) / 2.0;
+
+    assert!(sun_new_york.current_hour_angle(solar_noon).abs() < 0.01);
+    assert!(sun_new_york.current_hour_angle(solar_noon - 3.0) < 0.0);
+    assert!(sun_new_york.current_hour_angle(solar_noon + 3.0) > 0.0);
+}
+
+
+#[test]
+fn test_sunrise_and_sunset_azimuth_new_york() {
+    // March 20th 2024, the equinox
+    let sun_new_york = SunRiseAndSet::new()
+        .date(2024, 3, 20)
+        .long(-74.0060)
+        .lat(40.7128)
+        .timezone(-4.0);
+
+    assert!((sun_new_york.sunrise_azimuth().unwrap() - 90.0).abs() < 1.5);
+    assert!((sun_new_york.sunset_azimuth().unwrap() - 270.0).abs() < 1.5);
+
+    // May 16th 2024, the Sun rises north of east
+    let sun_new_york = sun_new_york.date(2024, 5, 16);
+    assert!(sun_new_york.sunrise_azimuth().unwrap() < 70.0);
+    assert!(sun_new_york.sunset_azimuth().unwrap() > 290.0);
+}
+
+
+#[test]
+fn test_raised_horizon_new_york() {
+    // May 16th 2024
+    let sun_new_york = SunRiseAndSet::new()
+        .date(2024, 5, 16)
+        .long(-74.0060)
+        .lat(40.7128)
+        .timezone(-4.0);
+
+    assert_eq!(sun_new_york.sunrise_time().unwrap(), sun_new_york.sunrise_time_by_horizon(0.0).unwrap());
+    assert_eq!(sun_new_york.sunset_time().unwrap(), sun_new_york.sunset_time_by_horizon(0.0).unwrap());
+
+    assert!(sun_new_york.sunrise_time_by_horizon(5.0).unwrap() > sun_new_york.sunrise_time().unwrap());
+    assert!(sun_new_york.sunset_time_by_horizon(5.0).unwrap() < sun_new_york.sunset_time().unwrap());
+    assert!(sun_new_york.sunrise_time_by_horizon(90.0).is_err());
+}
+
+
+#[test]
+fn test_local_ha_pair_new_york() {
+    // May 16th 2024
+    let sun_new_york = SunRiseAndSet::new()
+        .date(2024, 5, 16)
+        .long(-74.0060)
+        .lat(40.7128)
+        .timezone(-4.0);
+
+    let (rise, set) = sun_new_york.local_ha_pair().unwrap();
+    // Within a minute of the hour angles taken at the morning and evening declinations
+    assert!((rise - sun_new_york.sunrise_local_ha_in_deg().unwrap()).abs() < 1.0 / 60.0);
+    assert!((set - sun_new_york.sunset_local_ha_in_deg().unwrap()).abs() < 1.0 / 60.0);
+    // Symmetric about the meridian
+    assert!((rise + set - 24.0).abs() < 1e-4);
+
+    let polar_night = SunRiseAndSet::new().date(2024, 12, 21).long(18.9553).lat(69.6492).timezone(1.0);
+    assert!(matches!(polar_night.sunrise_local_ha_in_deg(), Err(SunMood::NeverRise)));
+    assert!(matches!(polar_night.local_ha_pair(), Err(SunMood::NeverRise)));
+}
+
+
+#[test]
+fn test_frac_doy_past_midnight() {
+    // Reykjavik on the June solstice, the Sun sets a few minutes after midnight of the 21st (day 173)
+    let reykjavik = SunRiseAndSet::new().date(2024, 6, 21).long(-21.9426).lat(64.1466).timezone(0.0);
+
+    let sunset = reykjavik.sunset_frac_doy().unwrap();
+    assert!(sunset > 174.0 && sunset < 174.01);
+    // The wrapped hour loses the day
+    assert!(reykjavik.sunset_time().unwrap() < 0.1);
+
+    let sunrise = reykjavik.sunrise_frac_doy().unwrap();
+    assert!((sunrise - (173.0 + reykjavik.sunrise_time().unwrap() as f64 / 24.0)).abs() < 1e-6);
+
+    let polar_night = SunRiseAndSet::new().date(2024, 12, 21).long(18.9553).lat(69.6492).timezone(1.0);
+    assert!(polar_night.sunset_frac_doy().is_err());
+}
+
+
+#[test]
+fn test_f64_precision_new_york() {
+    // May 16th 2024
+    let sun_new_york = SunRiseAndSet::new()
+        .date(2024, 5, 16)
+        .long(-74.0060)
+        .lat(40.7128)
+        .timezone(-4.0);
+
+    // An independent reference: the apparent Sun of Meeus (Chapter 25) and the sidereal time on UT (Meeus 12.4),
+    // with the time of the altitude -0.8333° found by bisection, giving 5:37:31.61 and 20:07:52.52
+    let (sunrise_reference, sunset_reference) = (5.625448, 20.131257);
+
+    // Both precisions stay within the 10 to 25 seconds of the algorithm itself
+    let sunrise = sun_new_york.sunrise_time_f64().unwrap();
+    let sunset = sun_new_york.sunset_time_f64().unwrap();
+    let sunrise_f32 = sun_new_york.sunrise_time().unwrap() as f64;
+    let sunset_f32 = sun_new_york.sunset_time().unwrap() as f64;
+    for time in [sunrise, sunrise_f32] {
+        assert!((time - sunrise_reference).abs() * 3600.0 < 25.0);
+    }
+    for time in [sunset, sunset_f32] {
+        assert!((time - sunset_reference).abs() * 3600.0 < 25.0);
+    }
+
+    // and differ from each other by the rounding of f32 alone, a few milliseconds
+    assert!((sunrise - sunrise_f32).abs() * 3600.0 < 0.01);
+    assert!((sunset - sunset_f32).abs() * 3600.0 < 0.01);
+
+    let polar_night = SunRiseAndSet::new().date(2024, 12, 21).long(18.9553).lat(69.6492).timezone(1.0);
+    assert!(matches!(polar_night.sunrise_time_f64(), Err(SunMood::NeverRise)));
+}
+
+
+#[test]
+fn test_equation_of_center() {
+    // May 16th 2024 in New York: the worked sunrise values of test_sun_rise_in_new_york
+    let sun_new_york = SunRiseAndSet {
+        doy: 137,
+        long: -74.0060,
+        lat: 40.7128,
+        timezone: -4.0,
+        ..Default::default()
+    };
+
+    let sma = sun_new_york.sunrise_mean_anomaly();
+    let stl = sun_new_york.sunrise_true_long_in_deg();
+    let center = equation_of_center(sma as f64);
+
+    assert!((center - 1.3997).abs() < 1e-3);
+    assert!(((sma as f64 + center + 282.634 - 360.0) - stl as f64).abs() < 1e-4);
+
+    // The Sun runs behind its mean position from aphelion to perihelion
+    assert!(equation_of_center(270.0) < -1.8);
+}
+
+#[test]
+fn test_limb_reference() {
+    // New York, May 16th 2024
+    let upper = SunRiseAndSet::new().date(2024, 5, 16).long(-74.0060).lat(40.7128).timezone(-4.0);
+    let center = upper.clone().limb(LimbReference::Center);
+    let lower = upper.clone().limb(LimbReference::LowerLimb);
+
+    // The upper limb is the default
+    assert_eq!(5.6219597, upper.sunrise_time().unwrap());
+
+    // The center comes up a minute and a half after the upper limb, and the lower limb as long again after it
+    let delay = (center.sunrise_time().unwrap() - upper.sunrise_time().unwrap()) * 60.0;
+    assert!((1.0..2.0).contains(&delay));
+    let lower_delay = (lower.sunrise_time().unwrap() - center.sunrise_time().unwrap()) * 60.0;
+    assert!((lower_delay - delay).abs() < 0.1);
+
+    // And it goes down earlier
+    assert!(center.sunset_time().unwrap() < upper.sunset_time().unwrap());
+    assert!(center.sunrise_time_f64().unwrap() > upper.sunrise_time_f64().unwrap());
+    // By then the Sun has moved a little further round towards the south
+    assert!(center.sunrise_azimuth().unwrap() > upper.sunrise_azimuth().unwrap());
+}
+
+#[test]
+fn test_solstice_noon_altitudes() {
+    assert!((summer_solstice_noon_altitude(40.0) - 73.44).abs() < 1e-9);
+    assert!((winter_solstice_noon_altitude(40.0) - 26.56).abs() < 1e-9);
+
+    // Mirrored in the southern hemisphere
+    assert!((summer_solstice_noon_altitude(-40.0) - 26.56).abs() < 1e-9);
+    assert!((winter_solstice_noon_altitude(-40.0) - 73.44).abs() < 1e-9);
+
+    // At the pole the Sun circles at its declination
+    assert!((summer_solstice_noon_altitude(90.0) - 23.44).abs() < 1e-9);
+    assert!((winter_solstice_noon_altitude(90.0) + 23.44).abs() < 1e-9);
+}
+
+#[test]
+fn test_sun_ecliptic_longitude() {
+    use astronav::time::AstroTime;
+
+    // The Sun reaches 60° on May 20th, ending its passage through Taurus, and is about 10° short of it early in May
+    let may = |day: u8| sun_ecliptic_longitude(AstroTime::new(day, 5, 2024, 0, 0, 0, 0.0).julian_time_tt());
+    assert!(may(20) < 60.0 && may(21) > 60.0);
+    assert!((may(10) - 50.0).abs() < 1.0);
+
+    // It advances by a little less than 1° a day around aphelion and a little more around perihelion
+    let daily = may(16) - may(15);
+    assert!((0.95..1.0).contains(&daily));
+    let january = |day: u8| sun_ecliptic_longitude(AstroTime::new(day, 1, 2024, 0, 0, 0, 0.0).julian_time_tt());
+    assert!((1.0..1.03).contains(&(january(4) - january(3))));
+
+    // And it is 0° at the March equinox, on March 20th 2024 at 03:06 UT
+    let equinox = sun_ecliptic_longitude(AstroTime::new(20, 3, 2024, 3, 6, 0, 0.0).julian_time_tt());
+    assert!(((equinox + 180.0).rem_euclid(360.0) - 180.0).abs() < 0.01);
+}
+
+#[test]
+fn test_latitude_and_longitude_ranges() {
+    let sun = SunRiseAndSet::new().date(2024, 5, 16).timezone(-4.0);
+
+    assert_eq!(InvalidLatitude(95.0), sun.clone().try_lat(95.0).unwrap_err());
+    assert_eq!(40.7128, sun.clone().try_lat(40.7128).unwrap().lat);
+    assert_eq!(-160.0, sun.clone().long(200.0).long);
+    assert_eq!(-74.006, sun.long(-74.006).long);
+}
+
+#[test]
+fn test_event_datetime_rolls_over() {
+    // Honolulu, the sun sets after 19:00 local time, which is already the next day in UT
+    let honolulu = SunRiseAndSet::new().date(2024, 5, 16).long(-157.8583).lat(21.3069).timezone(-10.0);
+    let (year, month, day, hour, min, sec) = honolulu.sunset_datetime(2024).unwrap();
+    assert_eq!((2024, 5, 17, 5), (year, month, day, hour));
+    let ut = hour as f32 + min as f32 / 60.0 + sec as f32 / 3600.0;
+    assert!((ut - (honolulu.sunset_time().unwrap() + 10.0 - 24.0)).abs() < 1e-3);
+
+    let new_years_eve = honolulu.date(2023, 12, 31);
+    assert_eq!((2024, 1, 1), new_years_eve.sunset_datetime(2023).map(|d| (d.0, d.1, d.2)).unwrap());
+
+    // Suva, just west of the date line, where the sun rises on the previous day in UT
+    let suva = SunRiseAndSet::new().date(2024, 5, 16).long(178.4419).lat(-18.1416).timezone(12.0);
+    let (year, month, day, hour, _, _) = suva.sunrise_datetime(2024).unwrap();
+    assert_eq!((2024, 5, 15, 18), (year, month, day, hour));
+}
+
+#[test]
+fn test_sun_horizontal_parallax() {
+    assert!((sun_horizontal_parallax(1.0) * 3600.0 - 8.79).abs() < 0.01);
+}
+
 #[cfg(feature = "noaa-sun")]
 mod noaa_sun {
-    use astronav::coords::{deg_to_hms, hours_to_hms, noaa_sun::{eot_in_mins_2, NOAASun}};
+    use astronav::coords::{deg_to_hms, hours_to_hms, math::to_cartesian, observer::ObserverLocation, noaa_sun::{civil_dawn_offset_minutes, CIVIL_ZENITH, SUNRISE_ZENITH, eot_in_mins_2, twilight_schedule, DeclinationModel, TwilightEvent, TwilightTime, ASTRONOMICAL_ZENITH, equation_of_time_year, solar_declination_for_day, solar_day_events, solar_day_events_by_horizon, solar_altitude_grid, sub_solar_point, SolarDayEvents, SunReport, terminator_points, NOAASun}};
+    use astronav::coords::sun::{sun_ecliptic_longitude, sun_horizontal_parallax};
+    use astronav::time::{day_of_year_to_date, mean_obliquity_in_degrees, AstroTime, SIDEREAL_RATE};
 
 
     #[test]
@@ -131,8 +375,9 @@ mod noaa_sun {
             lat: 13.0843,
             timezone: 5.5,
             hour: 13,
-            min: 08,
+            min: 8,
             sec: 47,
+            millis: 0,
         };
 
         let fy = chennai_sun.frac_year_by_hour_in_rads();
@@ -153,22 +398,745 @@ mod noaa_sun {
 
         assert_eq!(2.3354508228530677, fy);
         assert_eq!(3.6061869394507577, eot);
-        assert_eq!(19.251991, dec);
-        assert_eq!(15.672046917968146, ha);
-        assert_eq!("1:2:41.291313".to_owned(), deg_to_hms(ha as f32));
-        assert_eq!(54.1181633537517, ra);
-        assert_eq!("3:36:28.358917".to_owned(), deg_to_hms(ra as f32));        
-        assert_eq!(16.254976645690405, sza);
-        assert_eq!(73.7450233543096, alt);
-        assert_eq!(294.3440866717512, saa);
-        assert_eq!("5:43:2.9990387".to_owned(), hours_to_hms(sun_rise as f32));
-        assert_eq!(343.0499741423742, sun_rise_mins);
+        assert_eq!(19.166628, dec);
+        assert_eq!(15.867880251301472, ha);
+        assert_eq!("1:3:28.29134".to_owned(), deg_to_hms(ha as f32));
+        assert_eq!(53.92233002041837, ra);
+        assert_eq!("3:35:41.358948".to_owned(), deg_to_hms(ra as f32));        
+        assert_eq!(16.400376275114812, sza);
+        assert_eq!(73.59962372488519, alt);
+        assert_eq!(293.8367155355675, saa);
+        assert_eq!("5:43:8.476868".to_owned(), hours_to_hms(sun_rise as f32));
+        assert_eq!(343.1412772690191, sun_rise_mins);
         assert_eq!("12:5:18.709946".to_owned(), hours_to_hms(sun_noon as f32));
         assert_eq!(725.3118123281274, sun_noon_mins);
-        assert_eq!("18:27:34.41742".to_owned(), hours_to_hms(sun_set as f32));
-        assert_eq!(1107.5736505138805, sun_set_mins);
-        assert_eq!(12.742061272858436, day_length);
+        assert_eq!("18:27:28.937988".to_owned(), hours_to_hms(sun_set as f32));
+        assert_eq!(1107.4823473872357, sun_set_mins);
+        assert_eq!(12.73901783530361, day_length);
+
+    }
+
+    #[test]
+    fn test_max_altitude() {
+        // Chennai, May 17th 2024
+        let chennai_sun = NOAASun::new()
+            .date(2024, 5, 17)
+            .long(80.2705)
+            .lat(13.0843)
+            .timezone(5.5);
+
+        let noon_mins = chennai_sun.noon_mins();
+        let chennai_noon = chennai_sun
+            .hour((noon_mins / 60.0) as u8)
+            .min((noon_mins % 60.0) as u8)
+            .sec(((noon_mins * 60.0) % 60.0) as u8);
+
+        assert!((chennai_noon.max_altitude() - chennai_noon.altitude_in_deg()).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_solar_day_events_ordering() {
+        // New York, May 16th 2024
+        let events = solar_day_events(&ObserverLocation::new(40.7128, -74.0060), -4.0, (2024, 5, 16));
+
+        let ordered = [
+            events.astronomical_dawn,
+            events.nautical_dawn,
+            events.civil_dawn,
+            events.sunrise,
+            events.noon,
+            events.sunset,
+            events.civil_dusk,
+            events.nautical_dusk,
+            events.astronomical_dusk,
+        ]
+        .map(Option::unwrap);
+
+        assert!(ordered.windows(2).all(|w| w[0] < w[1]));
+
+        let sun = NOAASun::new().date(2024, 5, 16).long(-74.0060).lat(40.7128).timezone(-4.0).hour(12);
+        assert!((events.sunrise.unwrap() - sun.sunrise_time_hours()).abs() < 1e-9);
+        assert!((events.sunset.unwrap() - sun.sunset_time_hours()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_solar_day_events_polar() {
+        // Tromsø in midsummer, the Sun never sets and it never gets dark
+        let events = solar_day_events(&ObserverLocation::new(69.6492, 18.9553), 2.0, (2024, 6, 21));
+
+        assert_eq!(None, events.sunrise);
+        assert_eq!(None, events.sunset);
+        assert_eq!(None, events.astronomical_dusk);
+        assert!(events.noon.is_some());
+    }
+
+    #[test]
+    fn test_terminator_points() {
+        let time = AstroTime::new(17, 5, 2024, 13, 8, 47, 5.5);
+        let (sub_lat, sub_long) = sub_solar_point(&time);
+
+        // The Sun stands high above the Arabian Sea early in the afternoon in India
+        assert!((sub_lat - 19.48).abs() < 0.1);
+        assert!(sub_long > 60.0 && sub_long < 70.0);
+
+        let sub_solar = to_cartesian(sub_long, sub_lat);
+        let points = terminator_points(&time, 5.0).unwrap();
+
+        assert_eq!(72, points.len());
+        for (lat, long) in points {
+            let angle = to_cartesian(long, lat).dot(&sub_solar).clamp(-1.0, 1.0).acos().to_degrees();
+            assert!((angle - 90.0).abs() < 1e-6);
+            assert!(long > -180.0 && long <= 180.0);
+        }
+
+        // Steps that are not positive are rejected rather than asking for endless or no points
+        assert!(terminator_points(&time, 0.0).is_none());
+        assert!(terminator_points(&time, -1.0).is_none());
+        assert!(terminator_points(&time, f64::NAN).is_none());
+    }
+
+    #[test]
+    fn test_arc() {
+        // Chennai, May 17th 2024
+        let chennai_sun = NOAASun::new()
+            .date(2024, 5, 17)
+            .long(80.2705)
+            .lat(13.0843)
+            .timezone(5.5)
+            .hour(12);
+
+        let arc = chennai_sun.arc(1.0).unwrap();
+        assert_eq!(1440, arc.len());
+
+        let peak = arc.iter().fold(arc[0], |peak, &p| if p.1 > peak.1 { p } else { peak });
+
+        assert!((peak.1 - chennai_sun.max_altitude()).abs() < 0.01);
+        assert!((peak.0 - chennai_sun.noon_mins()).abs() <= 1.0);
+
+        // The Sun is below the horizon at midnight and in the east in the morning
+        assert!(arc[0].1 < 0.0);
+        assert!(arc[9 * 60].2 > 0.0 && arc[9 * 60].2 < 180.0);
+
+        // Steps that are not positive are rejected rather than asking for endless or no positions
+        assert!(chennai_sun.arc(0.0).is_none());
+        assert!(chennai_sun.arc(-5.0).is_none());
+        assert!(chennai_sun.arc(f64::NAN).is_none());
+    }
+
+    #[test]
+    fn test_positions_at() {
+        let chennai_sun = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+        let noon = chennai_sun.noon_hours();
+        let mins = noon * 60.0;
+        let at_noon = chennai_sun
+            .hour(noon as u8)
+            .min((mins % 60.0) as u8)
+            .millis(((mins % 1.0) * 60_000.0) as u16);
+
+        let positions = at_noon.positions_at(&[noon]);
+        assert_eq!(1, positions.len());
+        assert_eq!(noon, positions[0].0);
+        assert!((positions[0].1 - at_noon.altitude_in_deg()).abs() < 1e-4);
+        assert!((positions[0].2 - at_noon.azimuth_in_deg()).abs() < 1e-3);
+
+        // Morning in the east, afternoon in the west, and the times come back in the order given
+        let schedule = at_noon.positions_at(&[15.0, 9.0]);
+        assert_eq!((15.0, 9.0), (schedule[0].0, schedule[1].0));
+        assert!(schedule[0].2 > 180.0 && schedule[1].2 < 180.0);
+        assert!(schedule.iter().all(|p| p.1 > 0.0));
+    }
+
+    #[test]
+    fn test_raised_horizon_chennai() {
+        let chennai_sun = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+
+        assert!((chennai_sun.sunrise_time_mins_by_horizon(0.0).unwrap() - chennai_sun.sunrise_time_mins()).abs() < 1e-9);
+        assert!((chennai_sun.sunset_time_mins_by_horizon(0.0).unwrap() - chennai_sun.sunset_time_mins()).abs() < 1e-9);
+        assert!(chennai_sun.sunrise_time_mins_by_horizon(5.0).unwrap() > chennai_sun.sunrise_time_mins());
+        assert!(chennai_sun.sunset_time_mins_by_horizon(5.0).unwrap() < chennai_sun.sunset_time_mins());
+
+        let chennai = ObserverLocation::new(13.0843, 80.2705);
+        let events = solar_day_events(&chennai, 5.5, (2024, 5, 17));
+        let raised = solar_day_events_by_horizon(&chennai, 5.5, (2024, 5, 17), 5.0);
+        assert!(raised.sunrise.unwrap() > events.sunrise.unwrap());
+        assert!(raised.civil_dawn.unwrap() > events.civil_dawn.unwrap());
+        assert!(raised.sunset.unwrap() < events.sunset.unwrap());
+        assert!(raised.civil_dusk.unwrap() < events.civil_dusk.unwrap());
+        assert_eq!(events.noon, raised.noon);
+    }
+
+    #[test]
+    fn test_is_sun_above() {
+        let chennai_noon = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(12).min(5);
+        let chennai_midnight = chennai_noon.clone().hour(0).min(0);
+
+        assert!(chennai_noon.is_sun_above(10.0));
+        assert!(!chennai_noon.is_sun_above(89.0));
+        assert!(!chennai_midnight.is_sun_above(10.0));
+        assert!(!chennai_midnight.is_sun_above(0.0));
+        assert!(chennai_midnight.is_sun_above(-90.0));
+    }
+
+    #[test]
+    fn test_apparent_altitude() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+        // The moment the geometric center of the Sun is on the horizon
+        let sunrise = chennai.morning_time_mins_by_zenith(90.0).unwrap().round() as u16;
+        let chennai_sunrise = chennai.clone().hour((sunrise / 60) as u8).min((sunrise % 60) as u8);
+
+        let refraction = chennai_sunrise.apparent_altitude_in_deg() - chennai_sunrise.altitude_in_deg();
+        assert!(chennai_sunrise.altitude_in_deg().abs() < 0.3);
+        assert!((refraction - 0.55).abs() < 0.1);
+
+        // Refraction is only a fraction of an arc minute high in the sky
+        let chennai_noon = chennai.hour(12).min(5);
+        assert!(chennai_noon.apparent_altitude_in_deg() - chennai_noon.altitude_in_deg() < 0.01);
+    }
+
+    #[test]
+    fn test_lst_at_sunrise_and_sunset() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+
+        let lst_rise = chennai.lst_at_sunrise();
+        let lst_set = chennai.lst_at_sunset();
+        let day_length_in_sidereal_deg = chennai.day_length() * 15.0 * SIDEREAL_RATE;
+
+        assert!((0.0..360.0).contains(&lst_rise));
+        assert!(((lst_set - lst_rise).rem_euclid(360.0) - day_length_in_sidereal_deg).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_transit_lst() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+        let lst = chennai.transit_lst();
+        assert!((0.0..360.0).contains(&lst));
+
+        // The Sun at the moment of its transit
+        let noon = chennai.noon_mins();
+        let at_noon = chennai
+            .clone()
+            .hour((noon / 60.0) as u8)
+            .min((noon % 60.0) as u8)
+            .sec(((noon % 1.0) * 60.0) as u8);
+
+        // Within the half a minute the equation of time moves the noon by once taken at the hour of the transit
+        assert!(at_noon.ha_signed_hours().abs() < 0.01);
+        assert!(((at_noon.ra_in_deg() - lst + 180.0).rem_euclid(360.0) - 180.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_solar_declination_for_day() {
+        for doy in [1, 45, 80, 172, 266, 300, 356, 366] {
+            let (month, day) = astronav::time::day_of_year_to_date(2024, doy);
+            let sun = NOAASun::new().date(2024, month, day);
+
+            assert_eq!(sun.declination() as f64, solar_declination_for_day(doy));
+        }
+    }
+
+    #[test]
+    fn test_equation_of_time_year() {
+        let eot = equation_of_time_year(2024);
+        assert_eq!(366, eot.len());
+
+        let (min_doy, min) = eot.iter().cloned().fold((0, f64::MAX), |a, b| if b.1 < a.1 { b } else { a });
+        let (max_doy, max) = eot.iter().cloned().fold((0, f64::MIN), |a, b| if b.1 > a.1 { b } else { a });
+
+        // Around February 11th and November 3rd
+        assert!((38..=48).contains(&min_doy));
+        assert!((min + 14.2).abs() < 0.3);
+        assert!((302..=312).contains(&max_doy));
+        assert!((max - 16.4).abs() < 0.3);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_declination_by_model() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(13).min(8);
+
+        assert_eq!(chennai.declination() as f64, chennai.declination_by_model(DeclinationModel::AccurateCosine));
+        assert_eq!(chennai.declination_2(), chennai.declination_by_model(DeclinationModel::NoaaSpencer));
+        assert_eq!(chennai.declination_3(), chennai.declination_by_model(DeclinationModel::SpencerByDay));
+
+        // All the models agree within a few tenths of a degree
+        let accurate = chennai.declination_by_model(DeclinationModel::AccurateCosine);
+        assert!((accurate - chennai.declination_by_model(DeclinationModel::NoaaSpencer)).abs() < 0.3);
+        assert!((accurate - chennai.declination_by_model(DeclinationModel::SpencerByDay)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_latitude_and_longitude_ranges() {
+        let sun = NOAASun::new().date(2024, 5, 17).timezone(5.5);
+
+        assert!(sun.clone().try_lat(95.0).is_err());
+        assert!(sun.clone().try_lat(-90.0).is_ok());
+        assert_eq!(-160.0, sun.long(200.0).long);
+    }
+
+    #[test]
+    fn test_report() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(13).min(8).sec(47);
+        let report = chennai.report();
+
+        assert_eq!(chennai.altitude_in_deg(), report.altitude);
+        assert_eq!(chennai.azimuth_in_deg(), report.azimuth);
+        assert_eq!(chennai.ra_in_deg(), report.ra);
+        assert_eq!(chennai.declination() as f64, report.dec);
+        assert_eq!(chennai.ha_in_deg(), report.hour_angle);
+        assert_eq!(chennai.distance_in_au(), report.distance_au);
+        assert_eq!(chennai.eot_in_mins(), report.eot_minutes);
+        assert_eq!(chennai.sunrise_time_mins(), report.rise);
+        assert_eq!(chennai.noon_mins(), report.transit);
+        assert_eq!(chennai.sunset_time_mins(), report.set);
+
+        // The Earth is a little farther than 1 AU from the Sun in May
+        assert!((report.distance_au - 1.0114).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_twilight_schedule() {
+        let chennai = ObserverLocation::new(13.0843, 80.2705);
+        let schedule = twilight_schedule(&chennai, 5.5, (2024, 5, 17));
+
+        let events: Vec<TwilightEvent> = schedule.iter().map(|e| e.0).collect();
+        assert_eq!(
+            vec![
+                TwilightEvent::AstronomicalDawn,
+                TwilightEvent::NauticalDawn,
+                TwilightEvent::CivilDawn,
+                TwilightEvent::Sunrise,
+                TwilightEvent::Sunset,
+                TwilightEvent::CivilDusk,
+                TwilightEvent::NauticalDusk,
+                TwilightEvent::AstronomicalDusk,
+            ],
+            events
+        );
+        assert!(schedule.windows(2).all(|w| w[0].1 < w[1].1));
+
+        // Tromsø in midsummer, the Sun never sets
+        let tromso = ObserverLocation::new(69.6492, 18.9553);
+        let schedule = twilight_schedule(&tromso, 2.0, (2024, 6, 21));
+        assert!(schedule.is_empty());
+
+        // Oslo in midsummer, the Sun dips just below the civil twilight and never reaches the nautical one
+        let oslo = ObserverLocation::new(59.9139, 10.7522);
+        let events: Vec<TwilightEvent> = twilight_schedule(&oslo, 2.0, (2024, 6, 21)).iter().map(|e| e.0).collect();
+        assert_eq!(
+            vec![TwilightEvent::CivilDawn, TwilightEvent::Sunrise, TwilightEvent::Sunset, TwilightEvent::CivilDusk],
+            events
+        );
+    }
+
+    #[test]
+    fn test_civil_dawn_offset_grows_with_latitude() {
+        let offsets: Vec<f64> = [0.0, 20.0, 40.0, 55.0]
+            .iter()
+            .map(|lat| civil_dawn_offset_minutes(&ObserverLocation::new(*lat, 0.0), (2024, 3, 20)).unwrap())
+            .collect();
+
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+        assert!((offsets[0] - 21.0).abs() < 2.0);
+
+        // At 66° North the Sun does not set on midsummer
+        assert_eq!(None, civil_dawn_offset_minutes(&ObserverLocation::new(66.0, 0.0), (2024, 6, 21)));
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(13).min(8);
+        let report = chennai.report();
+        let row = report.to_csv_row();
+
+        assert_eq!(SunReport::csv_header().split(',').count(), row.split(',').count());
+        assert_eq!(Ok(report.altitude), row.split(',').next().unwrap().parse::<f64>());
+
+        let events = solar_day_events(&ObserverLocation::new(69.6492, 18.9553), 2.0, (2024, 6, 21));
+        let row = events.to_csv_row();
+        assert_eq!(SolarDayEvents::csv_header().split(',').count(), row.split(',').count());
+        // Only the noon happens in the midnight sun
+        assert_eq!(1, row.split(',').filter(|c| !c.is_empty()).count());
+    }
+
+    #[test]
+    fn test_sun_rise_and_set_accurate_model() {
+        use astronav::coords::sun::SunRiseAndSet;
+
+        let basic = SunRiseAndSet::new().date(2024, 5, 16).long(-74.0060).lat(40.7128).timezone(-4.0);
+        let accurate = basic.clone().with_accurate_model();
+
+        // An independent reference for New York on May 16th 2024: the apparent Sun of Meeus (Chapter 25) and the
+        // sidereal time on UT (Meeus 12.4), with the time of the altitude -0.8333° found by bisection to a millisecond.
+        // This gives the rise at 5:37:31.61 and the set at 20:07:52.52
+        let reference_rise = 5.625448;
+        let reference_set = 20.131257;
+
+        let basic_rise_error = (basic.sunrise_time().unwrap() as f64 - reference_rise).abs() * 3600.0;
+        let basic_set_error = (basic.sunset_time().unwrap() as f64 - reference_set).abs() * 3600.0;
+        let accurate_rise_error = (accurate.sunrise_time().unwrap() as f64 - reference_rise).abs() * 3600.0;
+        let accurate_set_error = (accurate.sunset_time().unwrap() as f64 - reference_set).abs() * 3600.0;
+
+        // About 12 and 6 seconds with the default model, under a second with the accurate one
+        assert!(basic_rise_error > 10.0 && basic_set_error > 5.0);
+        assert!(accurate_rise_error < 1.0);
+        assert!(accurate_set_error < 1.0);
+
+        // The accurate model needs the year, without it the default model is used
+        let doy_only = SunRiseAndSet { year: None, ..accurate.clone() };
+        assert_eq!(basic.sunrise_time().unwrap(), doy_only.sunrise_time().unwrap());
+    }
+
+    #[test]
+    fn test_ra_and_dec_rates() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(13).min(8);
+
+        // The Sun moves about 360° in a year, faster in RA near the solstices, slower near the equinoxes
+        let mean_rate = 360.0 / 365.2422 / 24.0;
+        assert!((chennai.ra_rate() - mean_rate).abs() < 0.1 * mean_rate);
+
+        // Still heading north in May, by about a quarter of a degree a day
+        assert!((chennai.dec_rate() * 24.0 - 0.25).abs() < 0.05);
+
+        // Interpolating an hour ahead
+        let later = chennai.clone().hour(14);
+        assert!((chennai.declination() as f64 + chennai.dec_rate() - later.declination() as f64).abs() < 1e-3);
+
+        // At local midnight of the new year the hour before falls in the previous year,
+        // and near the solstice the RA advances faster than the mean
+        let new_year = NOAASun::new().date(2024, 1, 1).long(80.2705).lat(13.0843).timezone(5.5);
+        assert!(new_year.ra_rate() > mean_rate);
+        assert!(new_year.ra_rate() < 1.2 * mean_rate);
+    }
+
+    #[test]
+    fn test_daylight_fraction() {
+        // A little over half at the equator on the equinox, because of refraction and the Sun's disc
+        let quito = NOAASun::new().date(2024, 3, 20).long(-78.4678).lat(-0.1807).timezone(-5.0);
+        assert!((quito.daylight_fraction() - 0.5).abs() < 0.01);
+        assert!(quito.daylight_fraction() > 0.5);
+        assert!((quito.daylight_fraction() * 24.0 - quito.day_length()).abs() < 1e-9);
+
+        // Midnight Sun and polar night in Tromsø
+        let tromso = NOAASun::new().long(18.9553).lat(69.6492).timezone(2.0);
+        assert_eq!(1.0, tromso.clone().date(2024, 6, 21).daylight_fraction());
+        assert_eq!(0.0, tromso.date(2024, 12, 21).daylight_fraction());
+    }
+
+    #[test]
+    fn test_solar_clock_offset() {
+        // Chennai is 80.27°E on a +5:30 zone centered on 82.5°E, so its sundials run about 9 minutes
+        // behind the clock, before the equation of time
+        let chennai = NOAASun::new().long(80.2705).lat(13.0843).timezone(5.5).hour(12);
+        let meridian_offset = 4.0 * 80.2705_f32 as f64 - 330.0;
+        assert!((meridian_offset + 8.918).abs() < 1e-3);
+
+        for date in [(2024, 2, 11), (2024, 4, 15), (2024, 11, 3)] {
+            let sun = chennai.clone().date(date.0, date.1, date.2);
+            assert!((sun.solar_clock_offset_minutes() - sun.eot_in_mins() - meridian_offset).abs() < 1e-9);
+        }
+
+        // Mid February the equation of time adds to the lag, early November it outweighs it
+        assert!(chennai.clone().date(2024, 2, 11).solar_clock_offset_minutes() < -20.0);
+        assert!(chennai.clone().date(2024, 11, 3).solar_clock_offset_minutes() > 7.0);
+
+        // At the local apparent noon, to the whole minute, the Sun is on the meridian
+        let noon = chennai.clone().date(2024, 4, 15);
+        let mins = 720.0 - noon.solar_clock_offset_minutes();
+        let ha = noon.hour((mins / 60.0) as u8).min((mins % 60.0) as u8).ha_in_deg();
+        assert!(ha.min(360.0 - ha) < 0.25);
+    }
+
+    #[test]
+    fn test_solar_altitude_grid() {
+        let time = AstroTime::new(17, 5, 2024, 13, 8, 47, 5.5);
+        let (sub_lat, sub_long) = sub_solar_point(&time);
+        let grid = solar_altitude_grid(&time, 1.0, 1.0).unwrap();
+        assert_eq!(181, grid.len());
+        assert!(grid.iter().all(|row| row.len() == 360));
+
+        // The highest Sun is at the grid point nearest to the sub-solar point
+        let (mut max, mut at) = (f64::MIN, (0, 0));
+        for (i, row) in grid.iter().enumerate() {
+            for (j, alt) in row.iter().enumerate() {
+                if *alt > max {
+                    (max, at) = (*alt, (i, j));
+                }
+            }
+        }
+        assert!(max > 89.0 && max <= 90.0);
+        assert!((at.0 as f64 - 90.0 - sub_lat).abs() <= 0.5);
+        assert!((at.1 as f64 - 180.0 - sub_long).abs() <= 0.5);
+
+        // Matches the altitude of the Sun seen from a grid point, here Chennai's nearest
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.0).lat(13.0).timezone(5.5).hour(13).min(8).sec(47);
+        assert!((grid[90 + 13][180 + 80] - chennai.altitude_in_deg()).abs() < 0.05);
+
+        // Steps that are not positive are rejected rather than looping without end or giving an empty grid
+        assert!(solar_altitude_grid(&time, 0.0, 1.0).is_none());
+        assert!(solar_altitude_grid(&time, 1.0, -10.0).is_none());
+        assert!(solar_altitude_grid(&time, f64::NAN, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_utc_times() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+        let zone = 5.5 * 60.0;
+
+        assert!((chennai.sunrise_time_mins_utc() + zone - chennai.sunrise_time_mins()).abs() < 1e-9);
+        assert!((chennai.noon_mins_utc() + zone - chennai.noon_mins()).abs() < 1e-9);
+        assert!((chennai.sunset_time_mins_utc() + zone - chennai.sunset_time_mins()).abs() < 1e-9);
+        let civil_dawn = chennai.morning_time_mins_by_zenith_utc(CIVIL_ZENITH).unwrap();
+        assert!((civil_dawn + zone - chennai.morning_time_mins_by_zenith(CIVIL_ZENITH).unwrap()).abs() < 1e-9);
+        let civil_dusk = chennai.evening_time_mins_by_zenith_utc(CIVIL_ZENITH).unwrap();
+        assert!((civil_dusk + zone - chennai.evening_time_mins_by_zenith(CIVIL_ZENITH).unwrap()).abs() < 1e-9);
+
+        // The UTC times barely depend on the timezone the observer reports, which only shifts the instant
+        // at which the declination and the equation of time are taken
+        let in_utc = chennai.clone().timezone(0.0);
+        assert!((in_utc.sunrise_time_mins() - chennai.sunrise_time_mins_utc()).abs() < 0.5);
+
+        // Chennai's Sun rises just after UTC midnight, Sydney's on the previous day in UTC
+        assert!(chennai.sunrise_time_mins_utc() > 0.0);
+        let sydney = NOAASun::new().date(2024, 5, 17).long(151.2093).lat(-33.8688).timezone(10.0);
+        assert!(sydney.sunrise_time_mins_utc() < 0.0);
+
+        let tromso = NOAASun::new().date(2024, 12, 21).long(18.9553).lat(69.6492).timezone(1.0);
+        assert_eq!(None, tromso.morning_time_mins_by_zenith_utc(SUNRISE_ZENITH));
+    }
+
+    #[test]
+    fn test_ha_signed_hours() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+
+        // Solar noon falls at 12:05 on the day
+        let noon = chennai.clone().hour(12).min(5);
+        assert!(noon.ha_signed_hours().abs() < 1.0 / 60.0);
+
+        let morning = chennai.clone().hour(8);
+        assert!((morning.ha_signed_hours() + 4.09).abs() < 0.01);
+        assert!((morning.ha_signed_hours() * 15.0 + 360.0 - morning.ha_in_deg()).abs() < 1e-9);
+
+        let evening = chennai.clone().hour(18);
+        assert!((evening.ha_signed_hours() - 5.91).abs() < 0.01);
+        assert!((evening.ha_signed_hours() * 15.0 - evening.ha_in_deg()).abs() < 1e-9);
+
+        // Around local midnight it stays within [-12, 12)
+        let midnight = chennai.hour(0);
+        assert!((-12.0..12.0).contains(&midnight.ha_signed_hours()));
+    }
+
+    #[test]
+    fn test_parallactic_angle() {
+        // New York in winter, the Sun crosses the meridian well south of the zenith
+        let new_york = NOAASun::new().date(2024, 1, 15).long(-74.0060).lat(40.7128).timezone(-5.0);
+        let noon_mins = new_york.noon_mins();
+        let at = |mins: f64| new_york.clone().hour((mins / 60.0) as u8).min((mins % 60.0) as u8).sec(((mins * 60.0) % 60.0) as u8);
+
+        assert!(at(noon_mins).parallactic_angle().abs() < 0.1);
+        assert!(at(noon_mins - 120.0).parallactic_angle() < -10.0);
+        assert!(at(noon_mins + 120.0).parallactic_angle() > 10.0);
+
+        // Mirrored about the noon
+        let morning = at(noon_mins - 180.0).parallactic_angle();
+        let afternoon = at(noon_mins + 180.0).parallactic_angle();
+        assert!((morning + afternoon).abs() < 0.5);
+
+        // In May at Chennai the Sun culminates north of the zenith
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(12).min(5).sec(19);
+        assert!(chennai.parallactic_angle().abs() > 170.0);
+    }
+
+    #[test]
+    fn test_frac_doy() {
+        // Reykjavik on the June solstice, the Sun sets a few minutes after midnight of day 173
+        let reykjavik = NOAASun::new().date(2024, 6, 21).long(-21.9426).lat(64.1466).timezone(0.0);
+        assert!(reykjavik.sunset_frac_doy() > 174.0 && reykjavik.sunset_frac_doy() < 174.01);
+        assert!((reykjavik.sunrise_frac_doy() - (173.0 + reykjavik.sunrise_time_hours() / 24.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_twilight_at_high_latitude() {
+        // At 70°N the Sun stays 3.4° above the horizon at the June solstice midnight, and 3.4° below it at the December noon
+        let june = NOAASun::new().date(2024, 6, 21).lat(70.0).long(0.0).timezone(0.0).hour(12);
+        assert_eq!(TwilightTime::AlwaysAboveThreshold, june.morning_twilight_by_zenith(ASTRONOMICAL_ZENITH));
+        assert_eq!(TwilightTime::AlwaysAboveThreshold, june.evening_twilight_by_zenith(SUNRISE_ZENITH));
+        assert_eq!(None, june.evening_twilight_by_zenith(CIVIL_ZENITH).mins());
+
+        let december = NOAASun::new().date(2024, 12, 21).lat(70.0).long(0.0).timezone(0.0).hour(12);
+        assert_eq!(TwilightTime::AlwaysBelowThreshold, december.morning_twilight_by_zenith(SUNRISE_ZENITH));
+        assert_eq!(TwilightTime::AlwaysBelowThreshold, december.evening_twilight_by_zenith(SUNRISE_ZENITH));
+
+        // The Sun still comes up to the civil twilight at noon
+        let civil_dawn = december.morning_twilight_by_zenith(CIVIL_ZENITH);
+        assert_eq!(december.morning_time_mins_by_zenith(CIVIL_ZENITH), civil_dawn.mins());
+        assert!(matches!(civil_dawn, TwilightTime::At(mins) if mins < december.noon_mins()));
+    }
+
+    #[test]
+    fn test_antisolar_point() {
+        // Chennai in the afternoon, and in the evening after the Sun set
+        for hour in [15, 20] {
+            let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(hour);
+
+            assert_eq!(-chennai.altitude_in_deg(), chennai.antisolar_altitude());
+            let offset = (chennai.antisolar_azimuth() - chennai.azimuth_in_deg()).rem_euclid(360.0);
+            assert!((offset - 180.0).abs() < 1e-9);
+            assert!((0.0..360.0).contains(&chennai.antisolar_azimuth()));
+        }
+
+        let night = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(20);
+        assert!(night.antisolar_altitude() > 0.0);
+    }
+
+    #[test]
+    fn test_geometric_day_length() {
+        // New York, May 16th 2024
+        let new_york = NOAASun::new().date(2024, 5, 16).long(-74.0060).lat(40.7128).timezone(-4.0).hour(12);
+        let geometric = new_york.geometric_day_length().unwrap();
+        let refraction_mins = (new_york.day_length() - geometric) * 60.0;
+        assert!((6.0..10.0).contains(&refraction_mins));
+
+        let morning = new_york.morning_time_mins_by_zenith(90.0).unwrap();
+        let evening = new_york.evening_time_mins_by_zenith(90.0).unwrap();
+        assert!((geometric * 60.0 - (evening - morning)).abs() < 1e-9);
+
+        // The Sun's center stays up all day at Tromsø in June
+        let tromso = NOAASun::new().date(2024, 6, 21).long(18.9553).lat(69.6492).timezone(2.0);
+        assert_eq!(None, tromso.geometric_day_length());
+    }
+
+    #[test]
+    fn test_precompute() {
+        for (hour, min) in [(6, 0), (12, 5), (13, 8), (20, 30)] {
+            let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(hour).min(min);
+            let cache = chennai.precompute();
+
+            assert_eq!(chennai.declination(), cache.declination());
+            assert_eq!(chennai.eot_in_mins(), cache.eot_in_mins());
+            assert_eq!(chennai.ha_in_deg(), cache.ha_in_deg());
+            assert_eq!(chennai.zenith_in_deg(), cache.zenith_in_deg());
+            assert_eq!(chennai.altitude_in_deg(), cache.altitude_in_deg());
+            assert_eq!(chennai.azimuth_in_deg(), cache.azimuth_in_deg());
+        }
+    }
+
+    #[test]
+    fn test_azimuth_swing() {
+        // Near the March equinox the Sun rises almost due east and sets almost due west
+        let new_york = NOAASun::new().date(2024, 3, 20).long(-74.0060).lat(40.7128).timezone(-4.0).hour(12);
+        assert!((new_york.sunrise_azimuth().unwrap() - 90.0).abs() < 2.0);
+        assert!((new_york.azimuth_swing() - 180.0).abs() < 3.0);
+
+        // In June it rises in the north-east and sets in the north-west
+        let june = NOAASun::new().date(2024, 6, 21).long(-74.0060).lat(40.7128).timezone(-4.0).hour(12);
+        assert!(june.sunrise_azimuth().unwrap() < 60.0);
+        assert!(june.azimuth_swing() > 235.0);
+
+        // In Sydney the June Sun passes through the north, over a short arc
+        let sydney = NOAASun::new().date(2024, 6, 21).long(151.2093).lat(-33.8688).timezone(10.0).hour(12);
+        let swing = sydney.azimuth_swing();
+        assert!((swing - (360.0 - (sydney.sunset_azimuth().unwrap() - sydney.sunrise_azimuth().unwrap()))).abs() < 1e-9);
+        assert!(swing < 130.0);
+
+        // The midnight sun goes all the way round
+        let tromso = NOAASun::new().date(2024, 6, 21).long(18.9553).lat(69.6492).timezone(2.0);
+        assert_eq!(None, tromso.sunrise_azimuth());
+        assert_eq!(360.0, tromso.azimuth_swing());
+        let tromso = NOAASun::new().date(2024, 12, 21).long(18.9553).lat(69.6492).timezone(1.0);
+        assert_eq!(0.0, tromso.azimuth_swing());
+    }
+
+    #[test]
+    fn test_declination_at_new_year() {
+        let at = |day: u8, timezone: f32, hour: u8| NOAASun::new().date(2024, 1, day).timezone(timezone).hour(hour);
+
+        // Early on January 1st in India the Sun is still on its December 31st UT course,
+        // and its declination follows on smoothly from the start of the year
+        let india = at(1, 5.5, 2);
+        assert!(india.frac_day_of_year() < 0.0);
+        assert!((india.declination() - at(1, 0.0, 0).declination()).abs() < 0.02);
+        assert!((india.declination() - -23.0).abs() < 0.1);
+
+        // Late on January 1st in California it is the same instant as January 2nd 4:00 UT
+        let california = at(1, -8.0, 20);
+        assert!((california.declination() - at(2, 0.0, 4).declination()).abs() < 1e-4);
+        assert!(california.declination() > india.declination());
+    }
+
+    #[test]
+    fn test_max_altitude_time() {
+        let new_york = |month: u8, day: u8| NOAASun::new().date(2024, month, day).long(-74.0060).lat(40.7128).timezone(-4.0);
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+
+        for sun in [new_york(3, 20), new_york(6, 21), new_york(9, 22), new_york(12, 21), chennai] {
+            assert!((sun.max_altitude_time() - sun.noon_mins()).abs() < 1.0);
+        }
+
+        // The Sun climbing north at the March equinox peaks later, compared to the transit, than at the September one
+        let march = new_york(3, 20).max_altitude_time() - new_york(3, 20).noon_mins();
+        let september = new_york(9, 22).max_altitude_time() - new_york(9, 22).noon_mins();
+        assert!(march > september);
+    }
+
+    #[test]
+    fn test_cardinal_positions() {
+        let chennai = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5);
+        let positions = chennai.cardinal_positions();
+
+        let labels: Vec<&str> = positions.iter().map(|p| p.0.as_str()).collect();
+        assert_eq!(vec!["sunrise", "mid-morning", "noon", "mid-afternoon", "sunset"], labels);
+
+        // The noon is the highest of the five, and the sun is on the horizon at its rise and set
+        assert!(positions.iter().all(|p| p.1 <= positions[2].1));
+        assert!((positions[2].1 - chennai.max_altitude()).abs() < 1e-6);
+        assert!((positions[0].1 + 0.833).abs() < 1e-6);
+        assert!((positions[4].1 + 0.833).abs() < 1e-6);
+
+        // Mirrored about the meridian
+        assert!((positions[1].1 - positions[3].1).abs() < 1e-9);
+        assert!((positions[0].2 + positions[4].2 - 360.0).abs() < 1e-6);
+        assert!((positions[0].2 - chennai.sunrise_azimuth().unwrap()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sun_horizontal_parallax_over_the_year() {
+        // Larger at perihelion in January than at aphelion in July
+        let january = NOAASun::new().date(2024, 1, 3).distance_in_au();
+        let july = NOAASun::new().date(2024, 7, 5).distance_in_au();
+        let (near, far) = (sun_horizontal_parallax(january) * 3600.0, sun_horizontal_parallax(july) * 3600.0);
+        assert!(near > 8.9 && far < 8.66);
+    }
+
+    #[test]
+    fn test_seconds_move_the_hour_angle() {
+        let chennai_sun = NOAASun::new().date(2024, 5, 17).long(80.2705).lat(13.0843).timezone(5.5).hour(13).min(8);
+
+        // The Sun moves 0.25° along its hour angle in a minute of clock time, give or take the tiny change of the equation of time
+        let shift = chennai_sun.clone().sec(30).ha_in_deg() - chennai_sun.ha_in_deg();
+        assert!((shift - 0.125).abs() < 1e-5);
+
+        let shift = chennai_sun.clone().millis(500).ha_in_deg() - chennai_sun.ha_in_deg();
+        assert!((shift - 0.125 / 60.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_declination_against_meeus() {
+        // The declination by the solar longitude of Meeus (Chapter 25) through 2024, four times a day
+        let mut worst: f64 = 0.0;
+        for doy in 1..=366 {
+            let (month, day) = day_of_year_to_date(2024, doy);
+            for hour in [0, 6, 12, 18] {
+                let jt = AstroTime::new(day, month, 2024, hour, 0, 0, 0.0).julian_time_tt();
+                let reference = (mean_obliquity_in_degrees(jt).to_radians().sin() * sun_ecliptic_longitude(jt).to_radians().sin())
+                    .asin()
+                    .to_degrees();
+                let dec = NOAASun::new().date(2024, month, day).hour(hour).declination() as f64;
+                worst = worst.max((dec - reference).abs());
+            }
+        }
 
+        // The former fractional day of the year, up to a day late by December, was up to 0.19° off
+        assert!(worst < 0.13);
     }
 
     #[test]
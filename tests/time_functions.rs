@@ -1,9 +1,9 @@
-use astronav::{coords::{deg_to_dms, deg_to_hms, dms_to_deg, hours_to_hms, hours_to_hms_tuple}, time::*};
+use astronav::{coords::{apparent_to_mean_solar, degrees_to_radians, radians_to_degrees, horizon_dip_degrees, horizon_distance_km, mean_to_apparent_solar, deg_to_dms_tuple, deg_to_dms_tuple_with, deg_to_hms_tuple_with, hours_to_hms_tuple_with, RoundMode, parse_dms_all, parse_dms_batch, parse_dms_components, parse_timezone, CoordParseError, decimal_hours_to_parts, decimal_hours_to_signed_parts, deg_to_dms, deg_to_hms, dms_to_deg, hms_to_deg, hours_to_hms, hours_to_hms_tuple}, time::*};
 
 #[test]
 fn test_time_methods() {
     // New york
-    let time = AstroTime { day: 12, month: 5, year: 2024, hour: 17, min: 30, sec: 45, timezone: -4.0 };
+    let time = AstroTime { day: 12, month: 5, year: 2024, hour: 17, min: 30, sec: 45, millis: 0, timezone: -4.0 };
     assert_eq!(2460443, time.julian_day_number());
     assert_eq!(2460443.3972106483, time.julian_time());
     assert_eq!(194.13824965432286, time.gmst_in_degrees());
@@ -14,7 +14,7 @@ fn test_time_methods() {
 
 #[test]
 fn test_time_methods_2() {
-    let time = AstroTime { day: 16, month: 5, year: 2024, hour: 13, min: 08, sec: 47, timezone: 5.5 };
+    let time = AstroTime { day: 16, month: 5, year: 2024, hour: 13, min: 8, sec: 47, millis: 0, timezone: 5.5 };
     assert_eq!(2460447, time.julian_day_number());
     assert_eq!(2460446.8194560185, time.julian_time());
     assert_eq!(349.5197100886144, time.gmst_in_degrees());
@@ -35,10 +35,7 @@ fn test_time_functions() {
 
 #[test]
 fn test_non_decimal_inputs_with_error() {
-    assert_eq!(
-        true,
-        dms_to_deg("-26-29:11.8").is_err()
-    );
+    assert!(dms_to_deg("-26-29:11.8").is_err());
 }
 
 #[test]
@@ -49,4 +46,390 @@ fn test_decimal_inputs() {
     assert_eq!("0:21:1.079979".to_owned(), deg_to_hms(5.2545));
     assert_eq!("14:19:59.998856".to_owned(), deg_to_hms(215.0));
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_decimal_hours_to_parts() {
+    // The sign of a value within an hour of zero is lost in the hours
+    assert_eq!((0, 30, 0, 0), decimal_hours_to_parts(-0.5));
+    assert_eq!((-1, 15, 0, 0), decimal_hours_to_parts(-1.25));
+    assert_eq!((-14, 0, 0, 0), decimal_hours_to_parts(-14.0));
+    assert_eq!((5, 37, 19, 55), decimal_hours_to_parts(5.6219597));
+    assert_eq!((1, 0, 0, 0), decimal_hours_to_parts(0.9999999));
+    assert_eq!((0, 0, 0, 0), decimal_hours_to_parts(-0.0));
+}
+
+#[test]
+fn test_decimal_hours_to_signed_parts() {
+    // The flag keeps the sign of -0.5h, which the hours of decimal_hours_to_parts cannot
+    let (negative, hours, min, sec, ms) = decimal_hours_to_signed_parts(-0.5);
+    assert!(negative);
+    assert_eq!((0, 30, 0, 0), (hours, min, sec, ms));
+
+    assert_eq!((false, 0, 30, 0, 0), decimal_hours_to_signed_parts(0.5));
+    assert_eq!((true, 1, 15, 0, 0), decimal_hours_to_signed_parts(-1.25));
+    assert_eq!((false, 0, 0, 0, 0), decimal_hours_to_signed_parts(-0.0));
+}
+
+#[test]
+fn test_with_offset() {
+    let time = AstroTime::new(12, 5, 2024, 17, 30, 45, -5.0);
+    let dst = time.with_offset(-4.0);
+
+    assert_eq!(-4.0, dst.timezone);
+    assert_eq!(time.julian_day_number(), dst.julian_day_number());
+    assert!((time.julian_time() - dst.julian_time() - 1.0 / 24.0).abs() < 1e-9);
+    assert!(time.gmst_in_degrees() != dst.gmst_in_degrees());
+}
+
+#[test]
+fn test_epochs() {
+    assert_eq!(2000.0, julian_epoch(2451545.0));
+    assert_eq!(2451545.0, jt_from_julian_epoch(2000.0));
+    assert!((besselian_epoch(2433282.4235) - 1950.0).abs() < 1e-6);
+    assert!((jt_from_besselian_epoch(1950.0) - 2433282.4235).abs() < 1e-3);
+    assert!((julian_epoch(jt_from_julian_epoch(2024.37)) - 2024.37).abs() < 1e-9);
+    assert!((besselian_epoch(jt_from_besselian_epoch(1875.0)) - 1875.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_besselian_year() {
+    // B1950.0 is JD 2433282.4235
+    assert!((besselian_year(2433282.4235) - 1950.0).abs() < 1e-6);
+    assert!((jt_from_besselian_epoch(besselian_year(2433282.4235)) - 2433282.4235).abs() < 1e-6);
+    assert_eq!(besselian_epoch(2460447.0), besselian_year(2460447.0));
+}
+
+#[test]
+fn test_lst_advance() {
+    // One solar day moves the sidereal time ahead by a little less than one degree
+    let lst = lst_advance(120.0, 86400.0);
+    assert!((lst - 120.98564736629).abs() < 1e-6);
+
+    assert!((lst_advance(359.5, 3600.0) - 14.541068).abs() < 1e-6);
+    assert!((lst_advance(0.5, -3600.0) - 345.458932).abs() < 1e-6);
+
+    // Stepping must agree with computing the sidereal time from scratch
+    let time = AstroTime { day: 12, month: 5, year: 2024, hour: 17, min: 30, sec: 45, millis: 0, timezone: -4.0 };
+    let later = AstroTime { hour: 19, min: 0, sec: 45, ..time.clone() };
+    let stepped = lst_advance(time.lmst_in_degrees(-74.0060), 5400.0);
+    assert!((stepped - later.lmst_in_degrees(-74.0060)).abs() < 1e-6);
+}
+
+#[test]
+fn test_julian_day_number_around_century_leap_days() {
+    assert_eq!(2451604, julian_day_number(29, 2, 2000));
+    assert_eq!(2451545, julian_day_number(1, 1, 2000));
+    assert_eq!(2451544, julian_day_number(31, 12, 1999));
+    assert_eq!(2415021, julian_day_number(1, 1, 1900));
+    assert_eq!(2415079, julian_day_number(28, 2, 1900));
+    assert_eq!(2415080, julian_day_number(1, 3, 1900));
+    assert_eq!(2488128, julian_day_number(28, 2, 2100));
+    assert_eq!(2488129, julian_day_number(1, 3, 2100));
+}
+
+#[test]
+fn test_ut_and_tt_scales() {
+    let time = AstroTime::new(12, 5, 2024, 17, 30, 45, -4.0);
+    let ut = time.julian_time_ut();
+    let tt = time.julian_time_tt();
+
+    assert!((tt - (ut + DELTA_T_SECS / 86400.0)).abs() < 1e-9);
+    assert_eq!(tt, time.julian_time());
+    assert_eq!(ut_to_tt(ut), tt);
+    assert!((tt_to_ut(tt) - ut).abs() < 1e-9);
+    assert_eq!(ut, julian_time_ut(2460443, 17, 30, 45, -4.0));
+    assert_eq!(tt, julian_time_tt(2460443, 17, 30, 45, -4.0));
+}
+
+#[test]
+fn test_millis() {
+    let time = AstroTime::new(12, 5, 2024, 17, 30, 45, -4.0);
+    let later = time.with_millis(500);
+
+    assert_eq!(0, time.millis());
+    assert_eq!(2460443.3972106483, time.julian_time());
+    assert!((later.julian_time() - time.julian_time() - 0.5 / 86400.0).abs() < 1e-9);
+
+    // Half a second of clock time turns the sky by about 0.00209°
+    let shift = later.lmst_in_degrees(-74.0060) - time.lmst_in_degrees(-74.0060);
+    assert!((shift - 0.5 * 360.98564736629 / 86400.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_parse_dms_components() {
+    assert_eq!(Ok((-26, 29, 11.8)), parse_dms_components("-26:29:11.8"));
+    assert_eq!(Ok((14, 16, 12.2)), parse_dms_components("14:16:12.2"));
+    assert_eq!(Ok((0, 30, 0.0)), parse_dms_components("-0:30:0"));
+
+    assert_eq!(Err(CoordParseError::WrongComponentCount(2)), parse_dms_components("-26-29:11.8"));
+    assert_eq!(Err(CoordParseError::InvalidNumber("x".to_owned())), parse_dms_components("x:29:11.8"));
+    assert_eq!(Err(CoordParseError::OutOfRange("60".to_owned())), parse_dms_components("12:60:0"));
+    assert_eq!(Err(CoordParseError::OutOfRange("61.5".to_owned())), parse_dms_components("12:0:61.5"));
+}
+
+#[test]
+fn test_plus_seconds_rolls_over() {
+    let time = AstroTime::new(31, 12, 2023, 23, 59, 30, 5.5).with_millis(250);
+
+    let later = time.plus_seconds(45);
+    assert_eq!((1, 1, 2024, 0, 0, 15, 250), (later.day, later.month, later.year, later.hour, later.min, later.sec, later.millis()));
+
+    let leap_day = AstroTime::new(1, 3, 2024, 0, 0, 0, 0.0).plus_seconds(-1);
+    assert_eq!((29, 2, 2024, 23, 59, 59), (leap_day.day, leap_day.month, leap_day.year, leap_day.hour, leap_day.min, leap_day.sec));
+
+    assert_eq!(time.julian_time() + 10.0, time.plus_seconds(10 * 86400).julian_time());
+}
+
+#[test]
+fn test_astro_time_range() {
+    let start = AstroTime::new(17, 5, 2024, 0, 0, 0, 5.5);
+    let end = AstroTime::new(17, 5, 2024, 23, 59, 0, 5.5);
+
+    let times: Vec<AstroTime> = start.range(&end, 60).collect();
+    assert_eq!(24, times.len());
+    for (i, t) in times.iter().enumerate() {
+        assert_eq!((17, i as u8, 0), (t.day, t.hour, t.min));
+    }
+
+    let backwards: Vec<AstroTime> = end.range(&start, -60).collect();
+    assert_eq!(24, backwards.len());
+    assert_eq!((0, 59), (backwards[23].hour, backwards[23].min));
+
+    assert_eq!(0, start.range(&end, 0).count());
+    assert_eq!(0, end.range(&start, 60).count());
+}
+
+#[test]
+fn test_sidereal_day_seconds() {
+    // 23h 56m 4.0905s
+    assert!((sidereal_day_seconds(2451545.0) - 86164.0905).abs() < 1e-3);
+    // The day of the polynomial barely changes over a century
+    assert!((sidereal_day_seconds(2451545.0 + 36525.0) - sidereal_day_seconds(2451545.0)).abs() < 1e-3);
+}
+
+#[test]
+fn test_julian_centuries() {
+    assert_eq!(0.0, julian_centuries(2451545.0));
+    assert_eq!(-1.0, julian_centuries(2451545.0 - 36525.0));
+
+    let time = AstroTime::new(17, 5, 2024, 13, 8, 47, 5.5);
+    assert!((time.julian_centuries_j2000() - 0.2437).abs() < 1e-4);
+    assert_eq!(julian_centuries(time.julian_time()), time.julian_centuries_j2000());
+}
+
+#[test]
+fn test_local_apparent_sidereal_time() {
+    let time = AstroTime::new(17, 5, 2024, 13, 8, 47, 5.5);
+    let eqeq = equation_of_equinoxes(time.julian_time());
+
+    let diff = time.last_in_degrees(80.2705) - time.lmst_in_degrees(80.2705);
+    assert!((diff - eqeq).abs() < 1e-9);
+    // Within 1.2 seconds of time
+    assert!(eqeq.abs() < 1.2 * 15.0 / 3600.0);
+}
+
+#[test]
+fn test_nutation() {
+    // Meeus, Example 22.a, 1987 April 10 at 0h TD: Δψ = -3.788", Δε = +9.443", ε0 = 23°26'27.407"
+    let jt = 2446895.5;
+    let (d_psi, d_eps) = nutation_in_degrees(jt);
+
+    assert!((d_psi * 3600.0 + 3.788).abs() < 0.5);
+    assert!((d_eps * 3600.0 - 9.443).abs() < 0.5);
+    assert!((mean_obliquity_in_degrees(jt) - (23.0 + 26.0 / 60.0 + 27.407 / 3600.0)).abs() < 1e-6);
+}
+
+#[test]
+fn test_negative_deg_to_hms() {
+    // -15.5° is -1h 2m 0s, short of the f32 rounding
+    let hms = deg_to_hms(-15.5);
+    assert_eq!("-1:1:59.999886", hms);
+    assert_eq!(deg_to_hms(15.5), hms.trim_start_matches('-'));
+
+    assert_eq!("-0:21:1.079979".to_owned(), deg_to_hms(-5.2545));
+    assert_eq!("-12:29:16.07872", hours_to_hms(-12.4878));
+}
+
+#[test]
+fn test_dms_to_deg_malformed_input() {
+    assert_eq!(Err(CoordParseError::Empty), dms_to_deg(""));
+    assert_eq!(Err(CoordParseError::Empty), dms_to_deg("   "));
+    assert_eq!(Err(CoordParseError::InvalidNumber("".to_owned())), dms_to_deg(":30:00"));
+    assert_eq!(Err(CoordParseError::InvalidNumber("-".to_owned())), dms_to_deg("-:30:00"));
+    assert_eq!(Err(CoordParseError::WrongComponentCount(2)), dms_to_deg("12:30"));
+
+    assert_eq!(Ok(12.5), dms_to_deg(" 12:30:00"));
+    assert_eq!(Ok(-12.5), dms_to_deg(" -12:30:00 "));
+}
+
+#[test]
+fn test_dms_and_hms_fields() {
+    // Each component is parsed as a number as before, only empty input and fields are errors
+    assert_eq!(Ok(12.5), dms_to_deg("12.5:0:0"));
+    assert_eq!(Ok(13.0), dms_to_deg("12:60:0"));
+    assert_eq!(Ok(-0.5), dms_to_deg("-0:30:0"));
+    assert_eq!(Ok(187.5), hms_to_deg("12.5:0:0"));
+
+    // hms_to_deg has the same guards instead of indexing into missing components
+    assert_eq!(Err(CoordParseError::WrongComponentCount(2)), hms_to_deg("16:30"));
+    assert_eq!(Err(CoordParseError::Empty), hms_to_deg(""));
+    assert_eq!(Err(CoordParseError::InvalidNumber("".to_owned())), hms_to_deg("16::0"));
+    assert_eq!(Err(CoordParseError::InvalidNumber("x".to_owned())), hms_to_deg("16:x:0"));
+    assert_eq!(Ok(247.5), hms_to_deg("16:30:00"));
+}
+
+#[test]
+fn test_parse_timezone() {
+    assert_eq!(Ok(5.5), parse_timezone("+05:30"));
+    assert_eq!(Ok(-4.0), parse_timezone("-04:00"));
+    assert_eq!(Ok(5.75), parse_timezone(" +05:45 "));
+    assert_eq!(Ok(0.0), parse_timezone("Z"));
+    assert_eq!(Ok(0.0), parse_timezone("+00:00"));
+
+    assert_eq!(Err(CoordParseError::Empty), parse_timezone(""));
+    assert_eq!(Err(CoordParseError::InvalidNumber("05:30".to_owned())), parse_timezone("05:30"));
+    assert_eq!(Err(CoordParseError::InvalidNumber("+5:30".to_owned())), parse_timezone("+5:30"));
+    assert_eq!(Err(CoordParseError::InvalidNumber("+0530".to_owned())), parse_timezone("+0530"));
+    assert_eq!(Err(CoordParseError::OutOfRange("75".to_owned())), parse_timezone("+05:75"));
+    assert!(parse_timezone("\u{2212}05:00").is_err());
+}
+
+#[test]
+fn test_parse_dms_batch() {
+    let inputs = ["-26:29:11.8", "14:16:12.2", "12:30", "", "38:47:x"];
+
+    let parsed = parse_dms_batch(&inputs);
+    assert_eq!(5, parsed.len());
+    assert_eq!(Ok(-26.48661111111111), parsed[0]);
+    assert_eq!(Ok(14.270055555555556), parsed[1]);
+    assert_eq!(Err(CoordParseError::WrongComponentCount(2)), parsed[2]);
+    assert_eq!(Err(CoordParseError::Empty), parsed[3]);
+    assert_eq!(Err(CoordParseError::InvalidNumber("x".to_owned())), parsed[4]);
+
+    assert_eq!(Ok(vec![-26.48661111111111, 14.270055555555556]), parse_dms_all(&inputs[..2]));
+    assert_eq!(Err((2, CoordParseError::WrongComponentCount(2))), parse_dms_all(&inputs));
+    assert_eq!(Ok(vec![]), parse_dms_all(&[]));
+}
+
+#[test]
+fn test_tuple_round_mode() {
+    // Just below a minute boundary the truncated seconds show 59.99..
+    assert_eq!((30, 59, 59.993134), deg_to_dms_tuple(30.999998));
+    assert_eq!((30, 59, 59.993134), deg_to_dms_tuple_with(30.999998, RoundMode::Truncate));
+    assert_eq!((31, 0, 0.0), deg_to_dms_tuple_with(30.999998, RoundMode::Nearest));
+
+    // The carry goes all the way into the hours
+    assert_eq!((5, 59, 59.99817), hours_to_hms_tuple_with(5.9999995, RoundMode::Truncate));
+    assert_eq!((6, 0, 0.0), hours_to_hms_tuple_with(5.9999995, RoundMode::Nearest));
+    assert_eq!((6, 0, 0.0), deg_to_hms_tuple_with(89.99999, RoundMode::Nearest));
+
+    // Away from a boundary only the seconds are rounded
+    assert_eq!((66, 30, 16.0), deg_to_dms_tuple_with(66.50446, RoundMode::Nearest));
+
+    // Past 255 both modes stop at 255 rather than wrapping around
+    assert_eq!((255, 30, 0.0), deg_to_dms_tuple_with(300.5, RoundMode::Truncate));
+    assert_eq!((255, 30, 0.0), deg_to_dms_tuple_with(300.5, RoundMode::Nearest));
+    assert_eq!((255, 0, 0.0), hours_to_hms_tuple_with(256.0, RoundMode::Nearest));
+    assert_eq!(RoundMode::Truncate, RoundMode::default());
+}
+
+#[test]
+fn test_apparent_and_mean_solar_time() {
+    for (hours, eot) in [(12.0, 16.5), (6.25, -14.2), (0.1, 10.0), (23.95, -8.0), (18.0, 0.0)] {
+        let mean = apparent_to_mean_solar(hours, eot);
+        assert!((0.0..24.0).contains(&mean));
+        assert!((mean_to_apparent_solar(mean, eot) - hours).abs() < 1e-9);
+        assert!((apparent_to_mean_solar(mean_to_apparent_solar(hours, eot), eot) - hours).abs() < 1e-9);
+    }
+
+    // Around midnight the time wraps into the other day
+    assert!((apparent_to_mean_solar(0.1, 10.0) - (24.0 + 0.1 - 10.0 / 60.0)).abs() < 1e-9);
+    assert!((mean_to_apparent_solar(23.95, 6.0) - 0.05).abs() < 1e-9);
+}
+
+#[test]
+fn test_horizon_distance() {
+    assert!((horizon_distance_km(100.0, 6371.0) - 35.7).abs() < 0.05);
+    assert_eq!(0.0, horizon_distance_km(0.0, 6371.0));
+    assert_eq!(0.0, horizon_dip_degrees(0.0, 6371.0));
+
+    // The distance grows with the square root of the height
+    let ratio = horizon_distance_km(400.0, 6371.0) / horizon_distance_km(100.0, 6371.0);
+    assert!((ratio - 2.0).abs() < 1e-4);
+
+    // The dip is the angle the horizon subtends at the center of the Earth
+    let dip = horizon_dip_degrees(100.0, 6371.0).to_radians();
+    assert!((dip * 6371.0 - horizon_distance_km(100.0, 6371.0)).abs() < 0.01);
+}
+
+#[test]
+fn test_frac_day_of_year_at_new_year() {
+    // Days since 0h UT on January 1st
+    assert_eq!(0.0, frac_day_of_year(2024, 1, 1, 0.0, 0.0));
+    assert_eq!(136.5, frac_day_of_year(2024, 5, 16, 0.0, 12.0));
+
+    // 2:00 in India is still 20:30 UT on December 31st
+    assert!((frac_day_of_year(2024, 1, 1, 5.5, 2.0) - -3.5 / 24.0).abs() < 1e-6);
+    // 20:00 in California is already 4:00 UT on January 2nd
+    assert!((frac_day_of_year(2024, 1, 1, -8.0, 20.0) - 28.0 / 24.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_degrees_and_radians() {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    assert_eq!(PI, degrees_to_radians(180.0));
+    assert_eq!(FRAC_PI_2, degrees_to_radians(90.0));
+    assert_eq!(-90.0, radians_to_degrees(-FRAC_PI_2));
+    assert_eq!(360.0, radians_to_degrees(2.0 * PI));
+    assert!((radians_to_degrees(degrees_to_radians(101.5504)) - 101.5504).abs() < 1e-12);
+}
+
+#[test]
+fn test_day_of_year_to_date_on_month_boundaries() {
+    assert_eq!((1, 1), day_of_year_to_date(2024, 1));
+    assert_eq!((1, 31), day_of_year_to_date(2024, 31));
+    assert_eq!((2, 1), day_of_year_to_date(2024, 32));
+    assert_eq!((2, 29), day_of_year_to_date(2024, 60));
+    assert_eq!((3, 1), day_of_year_to_date(2024, 61));
+    assert_eq!((3, 1), day_of_year_to_date(2023, 60));
+    assert_eq!((2, 28), day_of_year_to_date(2023, 59));
+    assert_eq!((12, 31), day_of_year_to_date(2024, 366));
+    assert_eq!((12, 31), day_of_year_to_date(2023, 365));
+
+    // Every day of a leap and a common year goes back to the same date
+    for (year, days) in [(2024, 366), (2023, 365)] {
+        for doy in 1..=days {
+            let (month, day) = day_of_year_to_date(year, doy);
+            assert_eq!(doy, day_of_year(year, month, day));
+        }
+    }
+}
+
+#[test]
+fn test_duration_since() {
+    // 21:00 in Chennai (15:30 UT), then 17:30 in London (16:30 UT) one hour later
+    let chennai = AstroTime::new(17, 5, 2024, 21, 0, 0, 5.5);
+    let london = AstroTime::new(17, 5, 2024, 17, 30, 0, 1.0);
+    assert_eq!(3600.0, london.duration_since(&chennai));
+    assert_eq!(-3600.0, chennai.duration_since(&london));
+    assert!((london.duration_since(&chennai) - (london.julian_time() - chennai.julian_time()) * 86400.0).abs() < 1e-3);
+
+    // Across midnight and the date line, to the millisecond
+    let new_york = AstroTime::new(16, 5, 2024, 23, 59, 59, -4.0).with_millis(250);
+    let later = new_york.plus_seconds(2).with_offset(-4.0);
+    assert_eq!(2.0, later.duration_since(&new_york));
+    assert_eq!(0.0, new_york.duration_since(&new_york));
+}
+
+#[test]
+fn test_gmst_time_scales() {
+    // April 10th 1987, 0h UT (Meeus, Example 12.a): 13h10m46.3668s
+    let time = AstroTime::new(10, 4, 1987, 0, 0, 0, 0.0);
+    assert!((gmst_in_degrees(time.julian_time_ut()) - 197.693195).abs() < 1e-5);
+
+    // The sidereal time of AstroTime is taken from its TT Julian Time, Delta T ahead
+    let ahead = time.gmst_in_degrees() - gmst_in_degrees(time.julian_time_ut());
+    assert!((ahead - DELTA_T_SECS * 360.98564736629 / 86400.0).abs() < 1e-6);
+}